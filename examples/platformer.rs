@@ -139,7 +139,7 @@ fn player_setup_system(mut commands: Commands)
 
     commands.spawn((Sepax::as_shape_bundle(&convex), player))
     .insert(Sepax { convex })
-    .insert(Movable { axes: Vec::new() })
+    .insert(Movable::default())
     .insert(Velocity { x: 0.0, y: 0.0 });
 
     commands.insert_resource(PlayerColliders { polygon: polygon, circle, aabb, gram, capsule, index: 0 });
@@ -179,7 +179,7 @@ fn velocity_correction_system(mut query: Query<(&mut Velocity, &Movable)>)
     for (mut velocity, correction) in query.iter_mut()
     {
 
-        for (_x, y) in correction.axes.iter()
+        for (_x, y) in correction.axes()
         {
 
             if y.abs() > f32::EPSILON && velocity.y.is_sign_positive() != y.is_sign_positive()
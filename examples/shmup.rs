@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::utils::HashSet;
 use bevy::window::WindowResolution;
 use bevy_prototype_lyon::prelude::*;
 use rand::prelude::*;
@@ -87,7 +88,6 @@ fn main()
     .add_systems(Update, player_movement_input_system)
     .add_systems(Update, player_shoot_input_system)
     .add_systems(Update, laser_velocity_system)
-    .add_systems(Update, laser_despawn_system)
     .add_systems(Update, laser_hit_system)
     .add_systems(Update, enemy_spawn_system)
     .add_systems(Update, enemy_velocity_system)
@@ -132,7 +132,7 @@ fn player_setup_system(mut commands: Commands)
 
     commands.spawn((Sepax::as_shape_bundle(&convex), player))
     .insert(Sepax { convex })
-    .insert(Movable { axes: Vec::new() })
+    .insert(Movable::default())
     .insert(Player);
 
 }
@@ -216,7 +216,8 @@ fn player_shoot_input_system(mut commands: Commands, player: Query<&Transform, W
 
                 commands.spawn((Sepax::as_shape_bundle(&convex), laser))
                 .insert(Sepax { convex })
-                .insert(Movable { axes: Vec::new() })
+                .insert(Movable::default())
+                .insert(Sensor)
                 .insert(Laser { x: LASER_SPEED * normal.0, y: LASER_SPEED * normal.1 });
 
             }
@@ -240,43 +241,43 @@ fn laser_velocity_system(mut lasers: Query<(&mut Transform, &Laser)>, time: Res<
 
 }
 
-fn laser_despawn_system(mut commands: Commands, query: Query<(Entity, &Movable), With<Laser>>)
+fn laser_hit_system(mut commands: Commands, mut events: EventReader<SepaxCollisionEvent>, lasers: Query<(), With<Laser>>, enemies: Query<(), With<Enemy>>, walls: Query<(), Without<Movable>>)
 {
 
-    for (entity, correction) in query.iter()
+    let mut hit = HashSet::new();
+
+    for event in events.read()
     {
 
-        //Collided with a non-Movable, therefore despawn
-        if correction.axes.len() > 0
-        {
+        let SepaxCollisionEvent::Started(first, second) = *event else { continue; };
 
-            commands.entity(entity).despawn();
-        
-        }
+        //Lasers and enemies are both Movable, so either one can be first
+        let (laser, other) = if lasers.contains(first) { (first, second) } else { (second, first) };
 
-    }
-
-}
+        if !lasers.contains(laser) || hit.contains(&laser)
+        {
 
-fn laser_hit_system(mut commands: Commands, lasers: Query<(Entity, &Sepax), (With<Laser>, Without<Enemy>)>, enemies: Query<(Entity, &Sepax), (With<Enemy>, Without<Laser>)>)
-{
+            continue;
 
-    for (laser, laser_sepax) in lasers.iter()
-    {
+        }
 
-        for (enemy, enemy_sepax) in enemies.iter()
+        //Lasers destroy the first enemy they hit, and are despawned by walls
+        if enemies.contains(other) && hit.insert(other)
         {
 
-            if sat_overlap(enemy_sepax.shape(), laser_sepax.shape())
-            {
+            commands.entity(other).despawn();
 
-                commands.entity(laser).despawn();
-                commands.entity(enemy).despawn();
+        }
+        else if !walls.contains(other)
+        {
 
-            }
+            continue;
 
         }
 
+        hit.insert(laser);
+        commands.entity(laser).despawn();
+
     }
 
 }
@@ -353,7 +354,7 @@ fn enemy_spawn_system(mut commands: Commands, time: Res<Time>, size: Res<WindowS
 
         commands.spawn((Sepax::as_shape_bundle(&convex), enemy))
         .insert(Sepax { convex })
-        .insert(Movable { axes: Vec::new() })
+        .insert(Movable::default())
         .insert(Enemy { x: velocity.0, y: velocity.1 });
 
     }
@@ -379,7 +380,7 @@ fn enemy_collision_system(mut query: Query<(&Movable, &mut Enemy)>)
     for (correction, mut enemy) in query.iter_mut()
     {
 
-        for (x, y) in correction.axes.iter()
+        for (x, y) in correction.axes()
         {
 
             let projection = (enemy.x * x) + (enemy.y * y);
@@ -0,0 +1,417 @@
+use bevy::prelude::*;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::asset::io::Reader;
+use bevy::utils::BoxedFuture;
+
+#[cfg(feature = "debug")]
+use bevy_prototype_lyon::prelude::*;
+
+use serde::Deserialize;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::Convex;
+use crate::components::{Sepax, SepaxCompound, SepaxLocal, SepaxOffset};
+
+/// The reasons that a collider asset cannot be loaded.
+#[derive(Debug)]
+pub enum SepaxAssetError
+{
+
+    /// The file could not be read.
+    Io(std::io::Error),
+
+    /// The file is not a valid collider asset.
+    Ron(ron::error::SpannedError)
+
+}
+
+impl std::fmt::Display for SepaxAssetError
+{
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+
+        match self
+        {
+
+            SepaxAssetError::Io(error) => write!(f, "could not read file: {}", error),
+            SepaxAssetError::Ron(error) => write!(f, "invalid collider asset: {}", error)
+
+        }
+
+    }
+
+}
+
+impl std::error::Error for SepaxAssetError {}
+
+impl From<std::io::Error> for SepaxAssetError
+{
+
+    fn from(error: std::io::Error) -> Self
+    {
+
+        SepaxAssetError::Io(error)
+
+    }
+
+}
+
+impl From<ron::error::SpannedError> for SepaxAssetError
+{
+
+    fn from(error: ron::error::SpannedError) -> Self
+    {
+
+        SepaxAssetError::Ron(error)
+
+    }
+
+}
+
+/// An asset storing named sets of shapes, loaded from `.collider.ron` files by the
+/// [`SepaxColliderLoader`](SepaxColliderLoader). Each set is given in an entity's local space and
+/// becomes the shape of every entity with a [`SepaxColliderHandle`](SepaxColliderHandle) naming it.
+///
+/// The shapes are written in the same form as the `serde` feature (de)serializes a
+/// [`Convex`](crate::Convex):
+///
+/// ```text
+/// (
+///     shapes:
+///     {
+///         "player": [Capsule((position: (0.0, 0.0), arm: (0.0, 8.0), radius: 6.0))],
+///         "ramp": [Polygon((position: (0.0, 0.0), vertices: [(0.0, 0.0), (32.0, 0.0), (32.0, 16.0)]))],
+///         "building":
+///         [
+///             AABB((position: (0.0, 0.0), width: 30.0, height: 10.0)),
+///             AABB((position: (0.0, 10.0), width: 10.0, height: 20.0))
+///         ]
+///     }
+/// )
+/// ```
+#[derive(Asset, TypePath, Clone, Debug, Default, Deserialize)]
+pub struct SepaxColliderAsset
+{
+
+    pub shapes: HashMap<String, Vec<Convex>>
+
+}
+
+impl SepaxColliderAsset
+{
+
+    /// Parse a collider asset from the contents of a `.collider.ron` file.
+    pub fn parse(bytes: &[u8]) -> Result<SepaxColliderAsset, SepaxAssetError>
+    {
+
+        Ok(ron::de::from_bytes(bytes)?)
+
+    }
+
+    /// The set of shapes with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&[Convex]>
+    {
+
+        self.shapes.get(name).map(Vec::as_slice)
+
+    }
+
+}
+
+/// Loads [`SepaxColliderAsset`](SepaxColliderAsset)s from files ending in `.collider.ron`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SepaxColliderLoader;
+
+impl AssetLoader for SepaxColliderLoader
+{
+
+    type Asset = SepaxColliderAsset;
+    type Settings = ();
+    type Error = SepaxAssetError;
+
+    fn load<'a>(&'a self, reader: &'a mut Reader, _settings: &'a (), _load_context: &'a mut LoadContext) -> BoxedFuture<'a, Result<SepaxColliderAsset, SepaxAssetError>>
+    {
+
+        Box::pin(async move
+        {
+
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            SepaxColliderAsset::parse(&bytes)
+
+        })
+
+    }
+
+    fn extensions(&self) -> &[&str]
+    {
+
+        &["collider.ron"]
+
+    }
+
+}
+
+/// A component which gives its entity the shape named `name` in a
+/// [`SepaxColliderAsset`](SepaxColliderAsset), once the asset has loaded. The positions in the
+/// file are offsets from the entity's origin. A set with a single shape becomes a
+/// [`Sepax`](crate::components::Sepax) whose position is kept as a
+/// [`SepaxOffset`](crate::components::SepaxOffset), while a set with several shapes becomes a
+/// [`SepaxCompound`](crate::components::SepaxCompound) whose parts keep their positions. Either
+/// way, the asset replaces any `SepaxOffset` that the entity already has.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+///
+/// fn spawn_system(mut commands: Commands, assets: Res<AssetServer>)
+/// {
+///
+///     let handle = assets.load("player.collider.ron");
+///     commands.spawn((SepaxColliderHandle::new(handle, "player"), Movable::default(), TransformBundle::default()));
+///
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct SepaxColliderHandle
+{
+
+    pub handle: Handle<SepaxColliderAsset>,
+    pub name: String
+
+}
+
+impl SepaxColliderHandle
+{
+
+    /// Create a component for the shapes with the given name in the given asset.
+    pub fn new(handle: Handle<SepaxColliderAsset>, name: impl Into<String>) -> SepaxColliderHandle
+    {
+
+        SepaxColliderHandle { handle, name: name.into() }
+
+    }
+
+}
+
+/// A plugin which registers the [`SepaxColliderAsset`](SepaxColliderAsset) and its loader, and adds
+/// the [`collider_asset_system`](collider_asset_system). It requires Bevy's `AssetPlugin`, so add
+/// it after the `DefaultPlugins`.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+///
+/// App::new()
+/// .add_plugins((DefaultPlugins, SepaxPlugin, SepaxAssetPlugin));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SepaxAssetPlugin;
+
+impl Plugin for SepaxAssetPlugin
+{
+
+    fn build(&self, app: &mut App)
+    {
+
+        app
+        .init_asset::<SepaxColliderAsset>()
+        .init_asset_loader::<SepaxColliderLoader>()
+        .add_systems(PreUpdate, collider_asset_system);
+
+    }
+
+}
+
+/// Gives each entity with a [`SepaxColliderHandle`](SepaxColliderHandle) its shapes once the asset
+/// has loaded, and again whenever the asset or the component changes, so that editing a
+/// `.collider.ron` file with hot reloading enabled updates every live entity using it. Entities
+/// whose set is missing from the asset are left unchanged.
+///
+/// The new components are placed in the world by the plugin's update systems on the same frame.
+/// With the `debug` feature, entities which are already rendered have their `Path` replaced with
+/// the new shape's outline, drawn at its offset.
+pub fn collider_asset_system
+(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<SepaxColliderAsset>>,
+    assets: Res<Assets<SepaxColliderAsset>>,
+    handles: Query<(Entity, Ref<SepaxColliderHandle>)>,
+    #[cfg(feature = "debug")] paths: Query<(), With<Path>>
+)
+{
+
+    let loaded: HashSet<AssetId<SepaxColliderAsset>> = events.read().filter_map(|event| match event
+    {
+
+        AssetEvent::Added { id } | AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+        AssetEvent::Removed { .. } => None
+
+    }).collect();
+
+    for (entity, collider) in handles.iter()
+    {
+
+        if !collider.is_changed() && !loaded.contains(&collider.handle.id())
+        {
+
+            continue;
+
+        }
+
+        let Some(shapes) = assets.get(&collider.handle).and_then(|asset| asset.get(&collider.name)) else { continue; };
+
+        match shapes
+        {
+
+            [] => continue,
+            [convex] =>
+            {
+
+                //The local copy is kept so that rotated and scaled entities are rebuilt from the new
+                //shape, and its position becomes the offset so that it is rotated and scaled too
+                let offset = convex.shape().position();
+                let mut local = convex.clone();
+                local.shape_mut().set_position((0.0, 0.0));
+
+                commands.entity(entity)
+                .insert((Sepax { convex: convex.clone() }, SepaxLocal { convex: local }, SepaxOffset(offset)))
+                .remove::<SepaxCompound>();
+
+                //Sepax::shape_geometry draws at the entity's origin, so the offset is drawn as a compound
+                #[cfg(feature = "debug")]
+                if paths.contains(entity)
+                {
+
+                    commands.entity(entity).insert(SepaxCompound::compound_geometry(shapes));
+
+                }
+
+            },
+            parts =>
+            {
+
+                commands.entity(entity)
+                .insert(SepaxCompound::new(parts.to_vec()))
+                .remove::<(Sepax, SepaxLocal, SepaxOffset)>();
+
+                #[cfg(feature = "debug")]
+                if paths.contains(entity)
+                {
+
+                    commands.entity(entity).insert(SepaxCompound::compound_geometry(parts));
+
+                }
+
+            }
+
+        }
+
+    }
+
+}
+
+#[cfg(test)]
+mod asset_tests
+{
+
+    use super::*;
+    use crate::float_equal;
+    use crate::plugin::SepaxPlugin;
+
+    use sepax2d::prelude::*;
+
+    const COLLIDERS: &str = r#"
+    (
+        shapes:
+        {
+            "ball": [Circle((position: (2.0, 0.0), radius: 4.0))],
+            "building":
+            [
+                AABB((position: (0.0, 0.0), width: 30.0, height: 10.0)),
+                AABB((position: (0.0, 10.0), width: 10.0, height: 20.0))
+            ]
+        }
+    )
+    "#;
+
+    fn app() -> App
+    {
+
+        let mut app = App::new();
+        app.add_plugins((bevy::core::TaskPoolPlugin::default(), AssetPlugin::default(), SepaxPlugin, SepaxAssetPlugin));
+
+        app
+
+    }
+
+    #[test]
+    fn test_parse()
+    {
+
+        let asset = SepaxColliderAsset::parse(COLLIDERS.as_bytes()).unwrap();
+
+        assert_eq!(asset.shapes.len(), 2);
+        assert_eq!(asset.get("building").map(<[Convex]>::len), Some(2));
+        assert!(matches!(asset.get("ball"), Some([Convex::Circle(circle)]) if circle.radius == 4.0));
+        assert!(asset.get("missing").is_none());
+
+        assert!(matches!(SepaxColliderAsset::parse(b"(shapes: {\"ball\": [Sphere(())]})"), Err(SepaxAssetError::Ron(_))));
+
+    }
+
+    #[test]
+    fn test_collider_asset_system()
+    {
+
+        let mut app = app();
+
+        let handle = app.world.resource_mut::<Assets<SepaxColliderAsset>>().add(SepaxColliderAsset::parse(COLLIDERS.as_bytes()).unwrap());
+
+        let ball = app.world.spawn((SepaxColliderHandle::new(handle.clone(), "ball"), Transform::from_xyz(5.0, 5.0, 0.0))).id();
+        let building = app.world.spawn(SepaxColliderHandle::new(handle.clone(), "building")).id();
+        let missing = app.world.spawn(SepaxColliderHandle::new(handle.clone(), "missing")).id();
+
+        app.update();
+
+        //The ball's position in the file is kept as its offset from the entity
+        assert!(matches!(app.world.get::<Sepax>(ball).map(|sepax| &sepax.convex), Some(Convex::Circle(circle)) if circle.radius == 4.0 && circle.position == (7.0, 5.0)));
+        assert!(matches!(app.world.get::<SepaxLocal>(ball).map(|local| &local.convex), Some(Convex::Circle(circle)) if circle.position == (0.0, 0.0)));
+        assert_eq!(app.world.get::<SepaxOffset>(ball), Some(&SepaxOffset((2.0, 0.0))));
+
+        //The offset is rotated along with the entity
+        app.world.get_mut::<Transform>(ball).unwrap().rotate_z(std::f32::consts::FRAC_PI_2);
+        app.update();
+
+        let position = app.world.get::<Sepax>(ball).unwrap().shape().position();
+        assert!(float_equal(position.0, 5.0) && float_equal(position.1, 7.0));
+
+        assert_eq!(app.world.get::<SepaxCompound>(building).map(|compound| compound.parts.len()), Some(2));
+        assert!(app.world.get::<Sepax>(missing).is_none() && app.world.get::<SepaxCompound>(missing).is_none());
+
+        //Hot reloading the asset turns the ball into a compound and the building into a single shape
+        {
+
+            let mut assets = app.world.resource_mut::<Assets<SepaxColliderAsset>>();
+            let asset = assets.get_mut(&handle).unwrap();
+
+            let building = asset.shapes.remove("building").unwrap();
+            asset.shapes.insert("ball".to_string(), building);
+            asset.shapes.insert("building".to_string(), vec![Convex::AABB(AABB::new((0.0, 0.0), 40.0, 40.0))]);
+
+        }
+
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.get::<SepaxCompound>(ball).map(|compound| compound.parts.len()), Some(2));
+        assert!(app.world.get::<Sepax>(ball).is_none() && app.world.get::<SepaxLocal>(ball).is_none() && app.world.get::<SepaxOffset>(ball).is_none());
+        assert!(matches!(app.world.get::<Sepax>(building).map(|sepax| &sepax.convex), Some(Convex::AABB(aabb)) if aabb.width == 40.0));
+        assert!(app.world.get::<SepaxCompound>(building).is_none());
+
+    }
+
+}
@@ -0,0 +1,381 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use sepax2d::Shape;
+
+use crate::Convex;
+
+//Shapes covering more cells than this are kept in a separate list instead of in every cell
+const LARGE_CELLS: f32 = 64.0;
+
+/// An axis-aligned bounding box given as its `(min, max)` corners.
+pub type Bounds = ((f32, f32), (f32, f32));
+
+/// Calculates the axis-aligned bounding box of any sepax2d shape by projecting it
+/// onto the coordinate axes.
+pub fn bounds(shape: &(impl Shape + ?Sized)) -> Bounds
+{
+
+    let x = shape.project((1.0, 0.0), true);
+    let y = shape.project((0.0, 1.0), true);
+
+    ((x.0, y.0), (x.1, y.1))
+
+}
+
+/// Calculates the axis-aligned bounding box containing every shape in the slice, such as
+/// the parts of a [`SepaxCompound`](crate::components::SepaxCompound). The bounds of an
+/// empty slice do not overlap anything.
+pub fn union_bounds(convexes: &[Convex]) -> Bounds
+{
+
+    convexes.iter().fold(((f32::INFINITY, f32::INFINITY), (f32::NEG_INFINITY, f32::NEG_INFINITY)), |union, convex|
+    {
+
+        let (min, max) = bounds(convex.shape());
+        ((f32::min(union.0.0, min.0), f32::min(union.0.1, min.1)), (f32::max(union.1.0, max.0), f32::max(union.1.1, max.1)))
+
+    })
+
+}
+
+/// Returns true if the two bounding boxes overlap or touch.
+pub fn bounds_overlap(left: Bounds, right: Bounds) -> bool
+{
+
+    left.0.0 <= right.1.0 && right.0.0 <= left.1.0 &&
+    left.0.1 <= right.1.1 && right.0.1 <= left.1.1
+
+}
+
+/// A uniform spatial hash containing every immovable [`Sepax`](crate::components::Sepax)
+/// entity in the world, keyed on the bounding box of its shape. When this resource is
+/// present, the [`collision_system`](crate::plugin::collision_system) only tests each
+/// [`Movable`](crate::components::Movable) against the immovable shapes in nearby cells,
+/// instead of every shape in the world.
+///
+/// The [`SepaxPlugin`](crate::plugin::SepaxPlugin) maintains this resource once you insert it,
+/// such as with `app.insert_resource(SpatialHash::new(64.0))`, or when it is added with
+/// [`SepaxPlugin::with_broadphase`](crate::plugin::SepaxPlugin::with_broadphase). Choose a cell size
+/// which is around the size of your typical shape: cells which are too small cause large
+/// shapes to be stored many times, while cells which are too large put many shapes in
+/// the same cell.
+///
+/// Shapes which would cover more than 64 cells, such as a huge floor or a kill plane, are kept
+/// in a separate list which every query checks, and queries which would cover more cells than
+/// there are entities check every entity instead, so neither ever visits a huge number of cells.
+#[derive(Resource)]
+pub struct SpatialHash
+{
+
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<Entity>>,
+    large: Vec<Entity>,
+    entries: HashMap<Entity, Bounds>
+
+}
+
+impl SpatialHash
+{
+
+    /// Create a new, empty spatial hash with the given cell size.
+    pub fn new(cell_size: f32) -> SpatialHash
+    {
+
+        SpatialHash { cell_size: f32::max(cell_size, f32::EPSILON), cells: HashMap::new(), large: Vec::new(), entries: HashMap::new() }
+
+    }
+
+    /// The width and height of each cell.
+    pub fn cell_size(&self) -> f32
+    {
+
+        self.cell_size
+
+    }
+
+    /// The number of entities stored in the hash.
+    pub fn len(&self) -> usize
+    {
+
+        self.entries.len()
+
+    }
+
+    /// Returns true if there are no entities stored in the hash.
+    pub fn is_empty(&self) -> bool
+    {
+
+        self.entries.is_empty()
+
+    }
+
+    /// Store the entity with the given bounding box, replacing its previous entry if
+    /// it was already present.
+    pub fn insert(&mut self, entity: Entity, bounds: Bounds)
+    {
+
+        self.remove(entity);
+        self.entries.insert(entity, bounds);
+
+        if self.is_large(bounds)
+        {
+
+            self.large.push(entity);
+            return;
+
+        }
+
+        let (min, max) = self.cell_range(bounds);
+        for x in min.0..=max.0
+        {
+
+            for y in min.1..=max.1
+            {
+
+                self.cells.entry((x, y)).or_default().push(entity);
+
+            }
+
+        }
+
+    }
+
+    /// Remove the entity from the hash, if present.
+    pub fn remove(&mut self, entity: Entity)
+    {
+
+        if let Some(bounds) = self.entries.remove(&entity)
+        {
+
+            if self.is_large(bounds)
+            {
+
+                self.large.retain(|other| *other != entity);
+                return;
+
+            }
+
+            let (min, max) = self.cell_range(bounds);
+            for x in min.0..=max.0
+            {
+
+                for y in min.1..=max.1
+                {
+
+                    if let Some(cell) = self.cells.get_mut(&(x, y))
+                    {
+
+                        cell.retain(|other| *other != entity);
+
+                        if cell.is_empty()
+                        {
+
+                            self.cells.remove(&(x, y));
+
+                        }
+
+                    }
+
+                }
+
+            }
+
+        }
+
+    }
+
+    /// Remove every entity from the hash.
+    pub fn clear(&mut self)
+    {
+
+        self.cells.clear();
+        self.large.clear();
+        self.entries.clear();
+
+    }
+
+    /// The entities whose bounding boxes overlap the given bounds, sorted and without
+    /// duplicates.
+    pub fn query(&self, bounds: Bounds) -> Vec<Entity>
+    {
+
+        if !self.worthwhile(bounds)
+        {
+
+            let mut found: Vec<Entity> = self.entries.iter().filter(|(_entity, other)| bounds_overlap(bounds, **other)).map(|(entity, _bounds)| *entity).collect();
+            found.sort_unstable();
+
+            return found;
+
+        }
+
+        let mut found: Vec<Entity> = self.large.iter().copied().filter(|entity| self.entries.get(entity).is_some_and(|other| bounds_overlap(bounds, *other))).collect();
+
+        let (min, max) = self.cell_range(bounds);
+        for x in min.0..=max.0
+        {
+
+            for y in min.1..=max.1
+            {
+
+                if let Some(cell) = self.cells.get(&(x, y))
+                {
+
+                    for entity in cell.iter()
+                    {
+
+                        if self.entries.get(entity).is_some_and(|other| bounds_overlap(bounds, *other))
+                        {
+
+                            found.push(*entity);
+
+                        }
+
+                    }
+
+                }
+
+            }
+
+        }
+
+        found.sort_unstable();
+        found.dedup();
+
+        found
+
+    }
+
+    //Large bounds cover more cells than there are entities, so checking every entity is faster
+    pub(crate) fn worthwhile(&self, bounds: Bounds) -> bool
+    {
+
+        let cells = self.cell_count(bounds);
+        cells.is_finite() && cells <= (self.len() as f32) + 1.0
+
+    }
+
+    fn is_large(&self, bounds: Bounds) -> bool
+    {
+
+        let cells = self.cell_count(bounds);
+        !cells.is_finite() || cells > LARGE_CELLS
+
+    }
+
+    //The number of cells the bounds cover, which is infinite or NaN for unbounded shapes
+    fn cell_count(&self, bounds: Bounds) -> f32
+    {
+
+        let width = f32::floor(bounds.1.0 / self.cell_size) - f32::floor(bounds.0.0 / self.cell_size) + 1.0;
+        let height = f32::floor(bounds.1.1 / self.cell_size) - f32::floor(bounds.0.1 / self.cell_size) + 1.0;
+
+        width * height
+
+    }
+
+    fn cell_range(&self, bounds: Bounds) -> ((i32, i32), (i32, i32))
+    {
+
+        let cell = |value: f32| f32::floor(value / self.cell_size) as i32;
+
+        ((cell(bounds.0.0), cell(bounds.0.1)), (cell(bounds.1.0), cell(bounds.1.1)))
+
+    }
+
+}
+
+#[cfg(test)]
+mod broadphase_tests
+{
+
+    use super::*;
+    use sepax2d::prelude::*;
+
+    #[test]
+    fn test_bounds()
+    {
+
+        let capsule = Capsule::new((1.0, 1.0), (0.0, 2.0), 1.0);
+        let ((min_x, min_y), (max_x, max_y)) = bounds(&capsule);
+
+        assert!((min_x - 0.0).abs() < 0.00001);
+        assert!((min_y + 2.0).abs() < 0.00001);
+        assert!((max_x - 2.0).abs() < 0.00001);
+        assert!((max_y - 4.0).abs() < 0.00001);
+
+    }
+
+    #[test]
+    fn test_union_bounds()
+    {
+
+        let convexes = [Convex::Circle(Circle::new((0.0, 0.0), 1.0)), Convex::AABB(AABB::new((5.0, -3.0), 2.0, 1.0))];
+        let ((min_x, min_y), (max_x, max_y)) = union_bounds(&convexes);
+
+        assert!((min_x + 1.0).abs() < 0.00001);
+        assert!((min_y + 3.0).abs() < 0.00001);
+        assert!((max_x - 7.0).abs() < 0.00001);
+        assert!((max_y - 1.0).abs() < 0.00001);
+
+        assert!(!bounds_overlap(union_bounds(&[]), ((-100.0, -100.0), (100.0, 100.0))));
+
+    }
+
+    #[test]
+    fn test_insert_query_remove()
+    {
+
+        let mut hash = SpatialHash::new(10.0);
+
+        let near = Entity::from_raw(0);
+        let far = Entity::from_raw(1);
+
+        hash.insert(near, bounds(&AABB::new((0.0, 0.0), 25.0, 5.0)));
+        hash.insert(far, bounds(&Circle::new((100.0, 100.0), 5.0)));
+
+        assert_eq!(hash.query(((20.0, 0.0), (21.0, 1.0))), vec![near]);
+        assert_eq!(hash.query(((96.0, 96.0), (97.0, 97.0))), vec![far]);
+        assert!(hash.query(((50.0, 50.0), (60.0, 60.0))).is_empty());
+
+        hash.insert(near, bounds(&AABB::new((50.0, 50.0), 5.0, 5.0)));
+        assert!(hash.query(((20.0, 0.0), (21.0, 1.0))).is_empty());
+        assert_eq!(hash.query(((50.0, 50.0), (60.0, 60.0))), vec![near]);
+
+        hash.remove(near);
+        assert!(hash.query(((50.0, 50.0), (60.0, 60.0))).is_empty());
+        assert_eq!(hash.len(), 1);
+
+    }
+
+    #[test]
+    fn test_large()
+    {
+
+        let mut hash = SpatialHash::new(1.0);
+
+        let floor = Entity::from_raw(0);
+        let plane = Entity::from_raw(1);
+        let small = Entity::from_raw(2);
+
+        //Neither shape is split across millions of cells
+        hash.insert(floor, ((-1.0e7, -1.0), (1.0e7, 0.0)));
+        hash.insert(plane, ((f32::NEG_INFINITY, -100.0), (f32::INFINITY, -99.0)));
+        hash.insert(small, ((5.0, 5.0), (6.0, 6.0)));
+
+        assert!(hash.cells.len() <= 4);
+        assert_eq!(hash.query(((3.0e6, -0.5), (3.0e6, -0.5))), vec![floor]);
+        assert_eq!(hash.query(((0.0, -100.0), (0.0, -100.0))), vec![plane]);
+
+        //Huge queries check every entity instead of every cell
+        assert_eq!(hash.query(((-f32::MAX, -f32::MAX), (f32::MAX, f32::MAX))), vec![floor, plane, small]);
+        assert_eq!(hash.query(((-f32::MAX, 10.0), (f32::MAX, 20.0))), vec![]);
+
+        hash.remove(plane);
+        assert!(hash.query(((0.0, -100.0), (0.0, -100.0))).is_empty());
+        assert_eq!(hash.len(), 2);
+
+    }
+
+}
@@ -9,6 +9,8 @@ use serde::{Serialize, Deserialize};
 use sepax2d::Shape;
 
 use crate::Convex;
+use crate::contact::ContactManifold;
+use crate::decompose::{decompose, DecomposeError};
 
 /// A component encapsulating a shape for collision detection. 
 /// A reference to the shape can be obtained using the [`shape`](Sepax::shape)
@@ -24,33 +26,334 @@ pub struct Sepax
 
 }
 
+/// A component storing the local, untransformed copy of an entity's shape. When syncing a
+/// [`Sepax`] with its entity's `Transform`, the plugin rebuilds the shape from this copy,
+/// scaling it by the `Transform`'s scale and rotating it around its position by the
+/// `Transform`'s rotation around the z-axis. See [`Convex::transformed`](crate::Convex::transformed)
+/// for how each shape is scaled and rotated.
+///
+/// You do not usually need to add this yourself: the first time that a `Sepax` is synced
+/// with a rotated or scaled `Transform`, its current shape is stored here. Once an entity has a
+/// `SepaxLocal`, change the shape through this component rather than the `Sepax`, as the
+/// `Sepax` is overwritten on each sync.
+#[derive(Clone, Component)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SepaxLocal
+{
+
+    pub convex: Convex
+
+}
+
+/// A component encapsulating several shapes which collide as a single body, such as an
+/// L-shaped building or a concave ship. Each of the `parts` is given in the entity's local
+/// space, with its position relative to the entity's origin, and the plugin places them in the
+/// world using the entity's `Transform` in the same way as a [`Sepax`] and its [`SepaxLocal`].
+///
+/// A compound entity should not also have a `Sepax` component. When it is [`Movable`], the
+/// corrections from all of its parts are merged and applied to the entity as a whole, and each
+/// collision is recorded in the `Movable` component.
+///
+/// ```rust
+/// use bevy_sepax2d::prelude::*;
+/// use sepax2d::prelude::*;
+///
+/// //An L-shaped building with its origin at the bottom left corner
+/// let building = SepaxCompound::new(vec!
+/// [
+///     Convex::AABB(AABB::new((0.0, 0.0), 30.0, 10.0)),
+///     Convex::AABB(AABB::new((0.0, 10.0), 10.0, 20.0))
+/// ]);
+///
+/// assert_eq!(building.shapes().len(), 2);
+/// ```
+#[derive(Clone, Component)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(from = "CompoundParts"))]
+pub struct SepaxCompound
+{
+
+    pub parts: Vec<Convex>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shapes: Vec<Convex>
+
+}
+
+//The serialized form of a SepaxCompound, whose world shapes are rebuilt from the parts
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct CompoundParts
+{
+
+    parts: Vec<Convex>
+
+}
+
+#[cfg(feature = "serde")]
+impl From<CompoundParts> for SepaxCompound
+{
+
+    fn from(compound: CompoundParts) -> Self
+    {
+
+        SepaxCompound::new(compound.parts)
+
+    }
+
+}
+
+impl SepaxCompound
+{
+
+    /// Create a compound from the given local shapes, placed as if the entity were at the origin.
+    pub fn new(parts: Vec<Convex>) -> SepaxCompound
+    {
+
+        let shapes = parts.clone();
+
+        SepaxCompound { parts, shapes }
+
+    }
+
+    /// Create a compound from a simple polygon, which may be concave, by decomposing it into
+    /// convex pieces with [`decompose`](crate::decompose::decompose). The vertices are given
+    /// relative to the entity's origin.
+    ///
+    /// Returns an error if the polygon intersects itself or has no area.
+    pub fn from_polygon(vertices: &[(f32, f32)]) -> Result<SepaxCompound, DecomposeError>
+    {
+
+        Ok(SepaxCompound::new(decompose(vertices)?))
+
+    }
+
+    /// The parts of the compound as they were last placed in the world.
+    pub fn shapes(&self) -> &[Convex]
+    {
+
+        &self.shapes
+
+    }
+
+    /// A mutable reference to the parts of the compound as they were last placed in the world.
+    /// Mostly used so that the parts can be moved together.
+    pub fn shapes_mut(&mut self) -> &mut [Convex]
+    {
+
+        &mut self.shapes
+
+    }
+
+    /// Places the parts in the world, scaling them by the given scale and rotating them around
+    /// the given position by the angle with the given sine and cosine. Each part is moved along
+    /// with the entity's local space, so the compound keeps its shape, and is then transformed
+    /// using [`Convex::transformed`](crate::Convex::transformed).
+    pub fn transform(&mut self, position: (f32, f32), sin: f32, cos: f32, scale: (f32, f32))
+    {
+
+        self.shapes = self.parts.iter().map(|part|
+        {
+
+            let local = part.shape().position();
+            let local = sepax2d::rotate!(sin, cos, (local.0 * scale.0, local.1 * scale.1));
+
+            part.transformed((position.0 + local.0, position.1 + local.1), sin, cos, scale)
+
+        }).collect();
+
+    }
+
+    /// A convenience method for obtaining the outline of every part as a single `Path`, in the
+    /// entity's local space. Capsules are drawn as polygons which approximate their caps.
+    ///
+    /// Requires "debug" feature.
+    #[cfg(feature = "debug")]
+    pub fn compound_geometry(parts: &[Convex]) -> Path
+    {
+
+        let mut path = ShapePath::new();
+
+        for part in parts.iter()
+        {
+
+            let position = part.shape().position();
+            let offset = Vec2::new(position.0, position.1);
+
+            path = match part
+            {
+
+                Convex::Polygon(poly) =>
+                {
+
+                    let points = poly.vertices.iter().map(|(x, y)| offset + Vec2::new(*x, *y)).collect();
+                    path.add(&shapes::Polygon { points, closed: true })
+
+                },
+                Convex::Parallelogram(gram) =>
+                {
+
+                    let points = vec![offset, offset + Vec2::new(gram.u.0, gram.u.1), offset + Vec2::new(gram.u.0 + gram.v.0, gram.u.1 + gram.v.1), offset + Vec2::new(gram.v.0, gram.v.1)];
+                    path.add(&shapes::Polygon { points, closed: true })
+
+                },
+                Convex::Circle(circle) =>
+                {
+
+                    path.add(&shapes::Circle { radius: circle.radius, center: offset })
+
+                },
+                Convex::AABB(aabb) =>
+                {
+
+                    let extents = Vec2::new(aabb.width, aabb.height);
+                    path.add(&shapes::Rectangle { extents, origin: RectangleOrigin::CustomCenter(offset + (extents * 0.5)) })
+
+                },
+                Convex::Capsule(capsule) =>
+                {
+
+                    let arm = capsule.arm();
+                    let angle = if arm == (0.0, 0.0) { 0.0 } else { f32::atan2(arm.1, arm.0) };
+
+                    //Each cap is approximated by a half circle of eight segments
+                    let mut points = Vec::new();
+                    for (end, start) in [(1.0, angle - std::f32::consts::FRAC_PI_2), (-1.0, angle + std::f32::consts::FRAC_PI_2)]
+                    {
+
+                        let center = offset + (Vec2::new(arm.0, arm.1) * end);
+                        for i in 0..=8
+                        {
+
+                            let theta = start + (std::f32::consts::PI * (i as f32) / 8.0);
+                            points.push(center + (Vec2::new(theta.cos(), theta.sin()) * capsule.radius));
+
+                        }
+
+                    }
+
+                    path.add(&shapes::Polygon { points, closed: true })
+
+                }
+
+            };
+
+        }
+
+        path.build()
+
+    }
+
+    /// A convenience method for obtaining the components necessary to draw every part of the
+    /// compound, with the entity at the given position. Like
+    /// [`Sepax::as_shape_bundle`], this does not insert the `SepaxCompound` itself.
+    ///
+    /// Requires "debug" feature.
+    #[cfg(feature = "debug")]
+    pub fn as_shape_bundle(&self, position: (f32, f32)) -> ShapeBundle
+    {
+
+        let path = SepaxCompound::compound_geometry(&self.parts);
+
+        ShapeBundle
+        {
+            path,
+            mesh: Default::default(),
+            spatial: SpatialBundle {
+                transform: Transform {
+                    translation: Vec3::new(position.0, position.1, 0.0),
+                    ..Default::default()
+                },
+                ..default()
+            },
+            ..Default::default()
+        }
+
+    }
+
+}
+
+/// A component which places an entity's shape at an offset from the entity's origin, such as
+/// a collider centered on the torso of a character whose origin is at its feet. The offset is
+/// given in the entity's local space, so it is scaled and rotated along with the entity.
+///
+/// When syncing with a `Transform`, the shape's position is set to the entity's translation
+/// plus the offset, and collision corrections are applied to the translation directly, so they
+/// are unaffected by the offset. Note that the position of an `AABB` or `Parallelogram` is one
+/// of its corners rather than its center.
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SepaxOffset(pub (f32, f32));
+
+/// A single collision of a [`Movable`] entity which was resolved by the Sepax plugin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CollisionRecord
+{
+
+    /// The entity which was collided with.
+    pub other: Entity,
+
+    /// The normalized axis of resolution, which points away from the other entity.
+    pub normal: (f32, f32),
+
+    /// How far the shapes overlapped along the normal before they were pushed apart.
+    pub depth: f32,
+
+    /// The average of the contact points.
+    pub point: (f32, f32),
+
+    /// The one or two points where the shapes touched after they were pushed apart.
+    pub contacts: ContactManifold
+
+}
+
+impl CollisionRecord
+{
+
+    /// Create a record of a collision with the given contact points.
+    pub fn new(other: Entity, normal: (f32, f32), depth: f32, contacts: ContactManifold) -> CollisionRecord
+    {
+
+        CollisionRecord { other, normal, depth, point: contacts.center(), contacts }
+
+    }
+
+}
+
 /// A component which denotes that the entity is moving and colliding
-/// with immovable entities. `axes` contains a list of the normalized collision
-/// resolution vectors which point away from the immovable object that was
-/// collided with. For example, if landing on flat ground, axes would contain
-/// (0,1) on the next frame.
+/// with immovable entities. `collisions` contains a record of each collision
+/// which was resolved, including the entity that was collided with and the
+/// normalized axis of resolution, which points away from that entity. For
+/// example, if landing on flat ground, the normal would be (0,1) on the next frame.
 ///
 /// This list is cleared during the `PostUpdate` stage each frame when utilizing 
 /// the plugin.
-#[derive(Component)]
+#[derive(Component, Clone, Debug, Default)]
 pub struct Movable
 {
 
-    pub axes: Vec<(f32, f32)>
+    pub collisions: Vec<CollisionRecord>
 
 }
 
 impl Movable
 {
 
+    ///The normalized axes of resolution of each collision.
+    pub fn axes(&self) -> impl Iterator<Item = (f32, f32)> + '_
+    {
+
+        self.collisions.iter().map(|record| record.normal)
+
+    }
+
     ///Convenience method for determining if there was a collision to the left of the object.
     pub fn left(&self) -> bool
     {
 
-        for (x, _y) in self.axes.iter()
+        for (x, _y) in self.axes()
         {
 
-            if *x > f32::EPSILON
+            if x > f32::EPSILON
             {
 
                 return true;
@@ -67,10 +370,10 @@ impl Movable
     pub fn right(&self) -> bool
     {
 
-        for (x, _y) in self.axes.iter()
+        for (x, _y) in self.axes()
         {
 
-            if *x < -f32::EPSILON
+            if x < -f32::EPSILON
             {
 
                 return true;
@@ -87,10 +390,10 @@ impl Movable
     pub fn above(&self) -> bool
     {
 
-        for (_x, y) in self.axes.iter()
+        for (_x, y) in self.axes()
         {
 
-            if *y < -f32::EPSILON
+            if y < -f32::EPSILON
             {
 
                 return true;
@@ -107,10 +410,10 @@ impl Movable
     pub fn below(&self) -> bool
     {
 
-        for (_x, y) in self.axes.iter()
+        for (_x, y) in self.axes()
         {
 
-            if *y > f32::EPSILON
+            if y > f32::EPSILON
             {
 
                 return true;
@@ -125,12 +428,170 @@ impl Movable
 
 }
 
+/// A component which opts a [`Movable`] into continuous collision detection, so that it cannot
+/// pass through a thin immovable shape when it moves a long way in a single frame, such as a
+/// laser or a fast-falling player. The plugin sweeps the shape from `previous`, its position at
+/// the end of the last frame, to its current position and stops it just before the first
+/// immovable shape in its path.
+///
+/// `previous` is recorded by the plugin each frame. Set it to `None` after teleporting the
+/// entity to skip the sweep for that frame.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ccd
+{
+
+    pub previous: Option<(f32, f32)>
+
+}
+
+/// A marker struct for immovable entities with an AABB shape which may be merged with their
+/// neighbours into larger shapes by the [`merge_system`](crate::merge::merge_system), such as the
+/// tiles of a level.
+#[derive(Component)]
+pub struct Mergeable;
+
 /// A marker struct which tells the Sepax plugin not to perform collision checks on
 /// the entity it is attached to. Collision checks can still be performed in custom
 /// systems.
 #[derive(Component)]
 pub struct NoCollision;
 
+/// A component which opts a [`Movable`] into collisions with other `Movable`s that also
+/// have a `Mass`. When two such entities overlap, the Sepax plugin pushes them apart, with
+/// the correction split between them in proportion to their inverse masses: a light crate
+/// is pushed further than a heavy player.
+///
+/// A mass which is infinite, zero, or negative is treated as infinitely heavy, so the entity
+/// is never pushed by other `Movable`s, but still pushes them.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mass(pub f32);
+
+impl Mass
+{
+
+    /// The inverse of the mass, which is zero for infinitely heavy entities.
+    pub fn inverse(&self) -> f32
+    {
+
+        if self.0 > f32::EPSILON && self.0.is_finite()
+        {
+
+            return 1.0 / self.0;
+
+        }
+
+        0.0
+
+    }
+
+}
+
+/// A marker struct which turns the entity's shape into a trigger zone, such as a checkpoint
+/// or a kill plane. The Sepax plugin checks sensors for overlap and reports it through
+/// [`SepaxCollisionEvent`](crate::events::SepaxCollisionEvent)s and the
+/// [`CollisionPairs`](crate::events::CollisionPairs) resource, but never pushes a
+/// [`Movable`] out of a sensor or pushes a `Movable` sensor out of a wall.
+#[derive(Component)]
+pub struct Sensor;
+
+/// A component which controls which entities collide with each other using bitmasks.
+/// `memberships` contains the layers that the entity belongs to, and `filters` contains
+/// the layers that it is allowed to collide with. Two entities only collide when each one
+/// belongs to a layer that the other one collides with.
+///
+/// Entities without a `CollisionLayers` component belong to and collide with every layer.
+///
+/// ```rust
+/// use bevy_sepax2d::prelude::*;
+///
+/// const WALLS: u32 = 1 << 0;
+/// const ENEMIES: u32 = 1 << 1;
+/// const BARRIERS: u32 = 1 << 2;
+/// const PLAYERS: u32 = 1 << 3;
+///
+/// let enemy = CollisionLayers::new(ENEMIES, WALLS | BARRIERS);
+/// let wall = CollisionLayers::new(WALLS, CollisionLayers::ALL.filters);
+/// let barrier = CollisionLayers::new(BARRIERS, ENEMIES);
+/// let player = CollisionLayers::new(PLAYERS, CollisionLayers::ALL.filters);
+///
+/// assert!(enemy.interacts(&wall));
+/// assert!(enemy.interacts(&barrier));
+/// assert!(!player.interacts(&barrier));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CollisionLayers
+{
+
+    pub memberships: u32,
+    pub filters: u32
+
+}
+
+impl CollisionLayers
+{
+
+    /// Belongs to and collides with every layer.
+    pub const ALL: CollisionLayers = CollisionLayers { memberships: u32::MAX, filters: u32::MAX };
+
+    /// Belongs to and collides with no layers.
+    pub const NONE: CollisionLayers = CollisionLayers { memberships: 0, filters: 0 };
+
+    /// Create layers with the given memberships and filters.
+    pub fn new(memberships: u32, filters: u32) -> CollisionLayers
+    {
+
+        CollisionLayers { memberships, filters }
+
+    }
+
+    /// Returns true if entities with these layers and the other layers should collide.
+    pub fn interacts(&self, other: &CollisionLayers) -> bool
+    {
+
+        (self.memberships & other.filters) != 0 && (other.memberships & self.filters) != 0
+
+    }
+
+    /// Like [`interacts`](CollisionLayers::interacts), but treats missing layers as
+    /// [`ALL`](CollisionLayers::ALL) for use with optional query parameters.
+    pub fn compatible(left: Option<&CollisionLayers>, right: Option<&CollisionLayers>) -> bool
+    {
+
+        left.unwrap_or(&CollisionLayers::ALL).interacts(right.unwrap_or(&CollisionLayers::ALL))
+
+    }
+
+}
+
+impl Default for CollisionLayers
+{
+
+    fn default() -> Self
+    {
+
+        CollisionLayers::ALL
+
+    }
+
+}
+
+//Reads whether an imported object is a sensor, and its layers, from its custom fields given as
+//text. A missing memberships or filters field is treated as every layer
+#[cfg(any(feature = "tiled", feature = "ldtk"))]
+pub(crate) fn read_fields<'a>(field: impl Fn(&str) -> Option<&'a str>, sensor: &str, memberships: &str, filters: &str) -> (bool, Option<CollisionLayers>)
+{
+
+    let layer = |name: &str| field(name).and_then(|value| value.parse::<u32>().ok());
+    let layers = (layer(memberships).is_some() || layer(filters).is_some())
+    .then(|| CollisionLayers::new(layer(memberships).unwrap_or(u32::MAX), layer(filters).unwrap_or(u32::MAX)));
+
+    (field(sensor) == Some("true"), layers)
+
+}
+
 impl Sepax
 {
 
@@ -140,16 +601,7 @@ impl Sepax
     pub fn shape(&self) -> &dyn Shape
     {
 
-        match &self.convex
-        {
-
-            Convex::Polygon(poly) => poly,
-            Convex::Circle(circle) => circle,
-            Convex::AABB(aabb) => aabb,
-            Convex::Parallelogram(gram) => gram,
-            Convex::Capsule(capsule) => capsule
-
-        }
+        self.convex.shape()
 
     }
 
@@ -160,16 +612,7 @@ impl Sepax
     pub fn shape_mut(&mut self) -> &mut dyn Shape
     {
 
-        match &mut self.convex
-        {
-
-            Convex::Polygon(poly) => poly,
-            Convex::Circle(circle) => circle,
-            Convex::AABB(aabb) => aabb,
-            Convex::Parallelogram(gram) => gram,
-            Convex::Capsule(capsule) => capsule
-
-        }
+        self.convex.shape_mut()
 
     }
 
@@ -296,19 +739,12 @@ impl Sepax
 
 }
 
-#[allow(dead_code)]
-fn float_equal(left: f32, right: f32) -> bool
-{
-
-    return (left - right).abs() < 0.00001;
-
-}
-
 #[cfg(test)]
 mod miscellaneous_tests
 {
 
     use super::*;
+    use crate::float_equal;
     use sepax2d::prelude::*;
 
     #[test]
@@ -324,12 +760,117 @@ mod miscellaneous_tests
 
     }
 
+    #[test]
+    fn test_rotation()
+    {
+
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let (sin, cos) = (f32::sin(quarter), f32::cos(quarter));
+
+        let polygon = Convex::Polygon(Polygon::from_vertices((0.0, 0.0), vec![(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]));
+        if let Convex::Polygon(poly) = polygon.transformed((5.0, 5.0), sin, cos, (1.0, 1.0))
+        {
+
+            assert!(float_equal(poly.position.0, 5.0));
+            assert!(float_equal(poly.vertices[0].0, 0.0));
+            assert!(float_equal(poly.vertices[0].1, 1.0));
+
+        }
+        else
+        {
+
+            panic!("Rotated polygon should remain a polygon");
+
+        }
+
+        let aabb = Convex::AABB(AABB::new((0.0, 0.0), 4.0, 2.0));
+        assert!(matches!(aabb.transformed((1.0, 1.0), 0.0, 1.0, (1.0, 1.0)), Convex::AABB(_)));
+
+        let flipped = aabb.transformed((1.0, 1.0), 0.0, -1.0, (1.0, 1.0));
+        assert!(float_equal(flipped.shape().position().0, -3.0));
+        assert!(float_equal(flipped.shape().position().1, -1.0));
+
+        let rotated = aabb.transformed((1.0, 1.0), sin, cos, (1.0, 1.0));
+        assert!(matches!(rotated, Convex::Parallelogram(_)));
+        assert!(contains_point(rotated.shape(), (0.0, 3.0)));
+        assert!(!contains_point(rotated.shape(), (3.0, 2.0)));
+
+    }
+
+    #[test]
+    fn test_compound()
+    {
+
+        let mut compound = SepaxCompound::new(vec!
+        [
+            Convex::AABB(AABB::new((0.0, 0.0), 30.0, 10.0)),
+            Convex::Circle(Circle::new((10.0, 20.0), 5.0))
+        ]);
+
+        //A quarter turn and double scale around (100, 100)
+        compound.transform((100.0, 100.0), 1.0, 0.0, (2.0, 2.0));
+
+        let shapes = compound.shapes();
+        assert!(matches!(shapes[0], Convex::Parallelogram(_)));
+        assert!(float_equal(shapes[0].shape().position().0, 100.0));
+        assert!(float_equal(shapes[0].shape().position().1, 100.0));
+        assert!(float_equal(shapes[1].shape().position().0, 60.0));
+        assert!(float_equal(shapes[1].shape().position().1, 120.0));
+
+        if let Convex::Circle(circle) = &shapes[1]
+        {
+
+            assert!(float_equal(circle.radius, 10.0));
+
+        }
+
+        //The local parts are unchanged
+        assert!(float_equal(compound.parts[1].shape().position().0, 10.0));
+
+    }
+
+    #[test]
+    fn test_scale()
+    {
+
+        let aabb = Convex::AABB(AABB::new((0.0, 0.0), 4.0, 2.0));
+        if let Convex::AABB(scaled) = aabb.transformed((0.0, 0.0), 0.0, 1.0, (-2.0, 0.5))
+        {
+
+            assert!(float_equal(scaled.position.0, -8.0));
+            assert!(float_equal(scaled.position.1, 0.0));
+            assert!(float_equal(scaled.width, 8.0));
+            assert!(float_equal(scaled.height, 1.0));
+
+        }
+        else
+        {
+
+            panic!("Unrotated AABB should remain an AABB");
+
+        }
+
+        let circle = Convex::Circle(Circle::new((0.0, 0.0), 2.0));
+        let Convex::Circle(scaled) = circle.transformed((0.0, 0.0), 0.0, 1.0, (1.5, -3.0)) else { panic!("Scaled circle should remain a circle"); };
+
+        assert!(float_equal(scaled.radius, 6.0));
+
+        let capsule = Convex::Capsule(Capsule::new((0.0, 0.0), (0.0, 2.0), 1.0));
+        let Convex::Capsule(scaled) = capsule.transformed((0.0, 0.0), 0.0, 1.0, (3.0, 2.0)) else { panic!("Scaled capsule should remain a capsule"); };
+
+        assert!(float_equal(scaled.arm().1, 4.0));
+        assert!(float_equal(scaled.radius(), 3.0));
+
+    }
+
     #[test]
     fn test_movable()
     {
 
-        let movable1 = Movable { axes: vec![(-1.0, 0.0), (0.0, 1.0)] };
-        let movable2 = Movable { axes: vec![(0.0, -1.0), (1.0, 0.0)] };
+        let record = |normal| CollisionRecord::new(Entity::PLACEHOLDER, normal, 1.0, ContactManifold::default());
+
+        let movable1 = Movable { collisions: vec![record((-1.0, 0.0)), record((0.0, 1.0))] };
+        let movable2 = Movable { collisions: vec![record((0.0, -1.0)), record((1.0, 0.0))] };
 
         assert!(movable1.right());
         assert!(movable1.below());
@@ -343,4 +884,29 @@ mod miscellaneous_tests
 
     }
 
+    #[test]
+    fn test_layers()
+    {
+
+        let enemy = CollisionLayers::new(0b010, 0b101);
+        let barrier = CollisionLayers::new(0b100, 0b010);
+
+        assert!(enemy.interacts(&barrier));
+        assert!(barrier.interacts(&enemy));
+        assert!(!barrier.interacts(&CollisionLayers::new(0b001, 0b100)));
+
+        assert!(CollisionLayers::compatible(None, Some(&barrier)));
+        assert!(!CollisionLayers::compatible(Some(&CollisionLayers::NONE), None));
+
+    }
+
+    #[test]
+    fn test_mass()
+    {
+
+        assert!(float_equal(Mass(4.0).inverse(), 0.25));
+        assert!(float_equal(Mass(f32::INFINITY).inverse(), 0.0));
+
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,288 @@
+use crate::Convex;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use sepax2d::prelude::*;
+
+const CONTACT_TOLERANCE: f32 = 0.01;
+
+/// The one or two points where a pair of shapes touch, such as a single point when a circle lands
+/// on the ground or two points when a box rests flat on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContactManifold
+{
+
+    points: [(f32, f32); 2],
+    count: usize
+
+}
+
+impl ContactManifold
+{
+
+    /// Create a manifold from up to two points. Any further points are ignored.
+    pub fn new(points: &[(f32, f32)]) -> ContactManifold
+    {
+
+        let mut manifold = ContactManifold::default();
+        for point in points.iter().take(2)
+        {
+
+            manifold.points[manifold.count] = *point;
+            manifold.count += 1;
+
+        }
+
+        manifold
+
+    }
+
+    /// The contact points.
+    pub fn points(&self) -> &[(f32, f32)]
+    {
+
+        //The count is clamped in case a deserialized manifold claims more points than it stores
+        &self.points[..self.count.min(2)]
+
+    }
+
+    /// The average of the contact points, or the origin if there are none.
+    pub fn center(&self) -> (f32, f32)
+    {
+
+        let points = self.points();
+
+        if points.is_empty()
+        {
+
+            return (0.0, 0.0);
+
+        }
+
+        let sum = points.iter().fold((0.0, 0.0), |sum, point| (sum.0 + point.0, sum.1 + point.1));
+        (sum.0 / (points.len() as f32), sum.1 / (points.len() as f32))
+
+    }
+
+}
+
+/// Finds the points where two overlapping or touching shapes meet, given the normalized
+/// normal pointing from the left shape towards the right shape, such as the direction of
+/// [`sat_collision`](sepax2d::sat_collision)`(left, right)`.
+///
+/// Each shape's feature facing the other is found: a side for polygons and for the flat side of a
+/// capsule, or a single point otherwise. When both features are sides, the side which faces the
+/// normal most directly is used as a reference and the other is clipped to it, keeping up to two
+/// points which are inside of or touching the reference shape. Otherwise, the contact is the single
+/// point.
+///
+/// ```rust
+/// use bevy_sepax2d::Convex;
+/// use bevy_sepax2d::contact::contact_manifold;
+/// use sepax2d::prelude::*;
+///
+/// let ground = Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0));
+/// let crate_box = Convex::AABB(AABB::new((2.0, 0.0), 4.0, 4.0));
+///
+/// let manifold = contact_manifold(&ground, &crate_box, (0.0, 1.0));
+/// assert_eq!(manifold.points(), &[(2.0, 0.0), (6.0, 0.0)]);
+/// ```
+pub fn contact_manifold(left: &Convex, right: &Convex, normal: (f32, f32)) -> ContactManifold
+{
+
+    let reverse = (-normal.0, -normal.1);
+
+    let left_feature = feature(left, normal);
+    let right_feature = feature(right, reverse);
+
+    match (left_feature.as_slice(), right_feature.as_slice())
+    {
+
+        ([left_point], [right_point]) => ContactManifold::new(&[((left_point.0 + right_point.0) / 2.0, (left_point.1 + right_point.1) / 2.0)]),
+        ([left_point], _) => ContactManifold::new(&[*left_point]),
+        (_, [right_point]) => ContactManifold::new(&[*right_point]),
+        ([a, b], [c, d]) =>
+        {
+
+            //The side which is closest to perpendicular to the normal is the reference
+            let facing = |p: &(f32, f32), q: &(f32, f32)|
+            {
+
+                let edge = (q.0 - p.0, q.1 - p.1);
+                let length = f32::sqrt((edge.0 * edge.0) + (edge.1 * edge.1));
+
+                if length > f32::EPSILON { ((edge.0 * normal.0) + (edge.1 * normal.1)).abs() / length } else { f32::INFINITY }
+
+            };
+
+            if facing(a, b) <= facing(c, d)
+            {
+
+                clip((*a, *b), normal, (*c, *d))
+
+            }
+            else
+            {
+
+                clip((*c, *d), reverse, (*a, *b))
+
+            }
+
+        },
+        _ => ContactManifold::default()
+
+    }
+
+}
+
+//The one or two points of the shape which are furthest in the given direction
+fn feature(convex: &Convex, direction: (f32, f32)) -> Vec<(f32, f32)>
+{
+
+    let side = |points: &[(f32, f32)]|
+    {
+
+        let dot = |point: &(f32, f32)| (point.0 * direction.0) + (point.1 * direction.1);
+        let max = points.iter().map(dot).fold(f32::NEG_INFINITY, f32::max);
+
+        let mut furthest: Vec<(f32, f32)> = points.iter().filter(|point| dot(point) >= max - CONTACT_TOLERANCE).copied().collect();
+
+        //Collinear vertices are reduced to the ends of the side
+        if furthest.len() > 2
+        {
+
+            let tangent = (-direction.1, direction.0);
+            let along = |point: &(f32, f32)| (point.0 * tangent.0) + (point.1 * tangent.1);
+
+            furthest.sort_by(|left, right| along(left).total_cmp(&along(right)));
+            furthest = vec![furthest[0], furthest[furthest.len() - 1]];
+
+        }
+
+        furthest
+
+    };
+
+    match convex
+    {
+
+        Convex::Polygon(_) | Convex::AABB(_) | Convex::Parallelogram(_) => side(&convex.vertices()),
+        Convex::Circle(_) => vec![convex.support(direction)],
+        Convex::Capsule(capsule) =>
+        {
+
+            let (position, arm, radius) = (capsule.position(), capsule.arm(), capsule.radius());
+            let ends = side(&[(position.0 + arm.0, position.1 + arm.1), (position.0 - arm.0, position.1 - arm.1)]);
+
+            ends.iter().map(|end| (end.0 + (direction.0 * radius), end.1 + (direction.1 * radius))).collect()
+
+        }
+
+    }
+
+}
+
+//Clips the incident side to the ends of the reference side, keeping the points which are inside
+//of or touching the reference shape, whose outward normal is given
+fn clip(reference: ((f32, f32), (f32, f32)), outward: (f32, f32), incident: ((f32, f32), (f32, f32))) -> ContactManifold
+{
+
+    let tangent = (-outward.1, outward.0);
+    let along = |point: (f32, f32)| (point.0 * tangent.0) + (point.1 * tangent.1);
+
+    let (low, high) = (f32::min(along(reference.0), along(reference.1)), f32::max(along(reference.0), along(reference.1)));
+    let (start, end) = (along(incident.0), along(incident.1));
+
+    let lerp = |time: f32| (incident.0.0 + ((incident.1.0 - incident.0.0) * time), incident.0.1 + ((incident.1.1 - incident.0.1) * time));
+    let clamp = |value: f32| if (end - start).abs() > f32::EPSILON { ((value - start) / (end - start)).clamp(0.0, 1.0) } else { 0.0 };
+
+    //Keep the points in the same order as the incident side
+    let (from, to) = (clamp(f32::max(f32::min(start, end), low)), clamp(f32::min(f32::max(start, end), high)));
+    let (first, second) = (lerp(f32::min(from, to)), lerp(f32::max(from, to)));
+
+    let depth = |point: &(f32, f32)| ((point.0 - reference.0.0) * outward.0) + ((point.1 - reference.0.1) * outward.1);
+
+    let points: Vec<(f32, f32)> = [first, second].into_iter().filter(|point| depth(point) <= CONTACT_TOLERANCE).collect();
+
+    if points.is_empty()
+    {
+
+        //Only reachable when the shapes do not touch, so use the nearest point
+        let nearest = if depth(&first) <= depth(&second) { first } else { second };
+        return ContactManifold::new(&[nearest]);
+
+    }
+
+    if points.len() == 2 && (points[0].0 - points[1].0).abs() <= f32::EPSILON && (points[0].1 - points[1].1).abs() <= f32::EPSILON
+    {
+
+        return ContactManifold::new(&points[..1]);
+
+    }
+
+    ContactManifold::new(&points)
+
+}
+
+#[cfg(test)]
+mod contact_tests
+{
+
+    use super::*;
+    use crate::float_equal;
+
+    #[test]
+    fn test_features()
+    {
+
+        let ground = Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0));
+
+        //A circle touches at a single point
+        let circle = Convex::Circle(Circle::new((3.0, 2.0), 2.0));
+        assert_eq!(contact_manifold(&ground, &circle, (0.0, 1.0)).points(), &[(3.0, 0.0)]);
+
+        //A box hanging off of the edge is clipped to the ground
+        let hanging = Convex::AABB(AABB::new((8.0, -0.5), 4.0, 4.0));
+        assert_eq!(contact_manifold(&ground, &hanging, (0.0, 1.0)).points(), &[(8.0, -0.5), (10.0, -0.5)]);
+
+        //A tilted box touches with its lowest corner
+        let tilted = Convex::Polygon(Polygon::from_vertices((0.0, 2.0), vec![(0.0, -2.0), (2.0, 0.0), (0.0, 2.0), (-2.0, 0.0)]));
+        assert_eq!(contact_manifold(&ground, &tilted, (0.0, 1.0)).points(), &[(0.0, 0.0)]);
+
+        //A capsule lying down touches along its side
+        let capsule = Convex::Capsule(Capsule::new((0.0, 1.0), (3.0, 0.0), 1.0));
+        let manifold = contact_manifold(&ground, &capsule, (0.0, 1.0));
+
+        assert_eq!(manifold.points().len(), 2);
+        assert!(float_equal(manifold.center().0, 0.0) && float_equal(manifold.center().1, 0.0));
+
+    }
+
+    #[test]
+    fn test_reference_side()
+    {
+
+        //The wall's side is clipped to the side of the small box
+        let wall = Convex::AABB(AABB::new((0.0, -10.0), 5.0, 20.0));
+        let small = Convex::AABB(AABB::new((-0.5, 0.0), 1.0, 1.0));
+
+        let manifold = contact_manifold(&small, &wall, (1.0, 0.0));
+        assert_eq!(manifold.points(), &[(0.0, 0.0), (0.0, 1.0)]);
+
+    }
+
+    #[test]
+    fn test_invalid_count()
+    {
+
+        //Such as a manifold deserialized from a hand-edited file
+        let manifold = ContactManifold { points: [(1.0, 1.0), (3.0, 1.0)], count: 5 };
+
+        assert_eq!(manifold.points().len(), 2);
+        assert_eq!(manifold.center(), (2.0, 1.0));
+
+    }
+
+}
@@ -0,0 +1,466 @@
+use sepax2d::prelude::*;
+
+use crate::Convex;
+
+const EPSILON: f32 = 0.00001;
+
+/// The reasons that a polygon cannot be decomposed into convex pieces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecomposeError
+{
+
+    /// The polygon has fewer than three distinct vertices.
+    TooFewVertices,
+
+    /// The polygon has no area, such as when all of its vertices lie on a line, or is too close
+    /// to degenerate to be split into triangles.
+    Degenerate,
+
+    /// Two of the polygon's edges cross or touch, other than neighbouring edges at their
+    /// shared vertex.
+    SelfIntersecting
+
+}
+
+impl std::fmt::Display for DecomposeError
+{
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+
+        match self
+        {
+
+            DecomposeError::TooFewVertices => write!(f, "polygon has fewer than three distinct vertices"),
+            DecomposeError::Degenerate => write!(f, "polygon has no area"),
+            DecomposeError::SelfIntersecting => write!(f, "polygon intersects itself")
+
+        }
+
+    }
+
+}
+
+impl std::error::Error for DecomposeError {}
+
+/// Decomposes a simple polygon, which may be concave, into convex polygons which exactly cover
+/// it. The vertices may be given in either winding order, and each resulting polygon has its
+/// position at the origin with its vertices in counterclockwise order, so the pieces can be used
+/// directly as the parts of a [`SepaxCompound`](crate::components::SepaxCompound).
+///
+/// The polygon is first triangulated by ear clipping, and the triangles are then merged using
+/// the Hertel–Mehlhorn algorithm, which removes diagonals while the pieces on either side stay
+/// convex. This produces at most four times as many pieces as the fewest possible.
+///
+/// Returns an error if the polygon intersects itself or has no area.
+///
+/// ```rust
+/// use bevy_sepax2d::decompose::decompose;
+///
+/// let l_shape = [(0.0, 0.0), (30.0, 0.0), (30.0, 10.0), (10.0, 10.0), (10.0, 30.0), (0.0, 30.0)];
+/// assert_eq!(decompose(&l_shape).unwrap().len(), 2);
+///
+/// let bowtie = [(0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0)];
+/// assert!(decompose(&bowtie).is_err());
+/// ```
+pub fn decompose(vertices: &[(f32, f32)]) -> Result<Vec<Convex>, DecomposeError>
+{
+
+    let mut points: Vec<(f32, f32)> = Vec::with_capacity(vertices.len());
+    for vertex in vertices.iter()
+    {
+
+        if !points.last().is_some_and(|last| same(*last, *vertex))
+        {
+
+            points.push(*vertex);
+
+        }
+
+    }
+
+    while points.len() > 1 && same(points[0], points[points.len() - 1])
+    {
+
+        points.pop();
+
+    }
+
+    if points.len() < 3
+    {
+
+        return Err(DecomposeError::TooFewVertices);
+
+    }
+
+    let area = signed_area(&points);
+    if area.abs() <= EPSILON
+    {
+
+        return Err(DecomposeError::Degenerate);
+
+    }
+
+    if self_intersects(&points)
+    {
+
+        return Err(DecomposeError::SelfIntersecting);
+
+    }
+
+    if area < 0.0
+    {
+
+        points.reverse();
+
+    }
+
+    let pieces = merge(triangulate(&points)?, &points);
+
+    Ok(pieces.into_iter().map(|piece|
+    {
+
+        let vertices = piece.iter().map(|index| points[*index]).collect();
+        Convex::Polygon(Polygon::from_vertices((0.0, 0.0), simplify(vertices)))
+
+    }).collect())
+
+}
+
+//Twice the signed area of the polygon, which is positive for counterclockwise polygons
+fn signed_area(points: &[(f32, f32)]) -> f32
+{
+
+    let mut area = 0.0;
+    for i in 0..points.len()
+    {
+
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        area += (a.0 * b.1) - (b.0 * a.1);
+
+    }
+
+    area
+
+}
+
+//Positive if c is to the left of the line from a to b
+fn cross(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32
+{
+
+    ((b.0 - a.0) * (c.1 - a.1)) - ((b.1 - a.1) * (c.0 - a.0))
+
+}
+
+fn same(a: (f32, f32), b: (f32, f32)) -> bool
+{
+
+    (a.0 - b.0).abs() <= EPSILON && (a.1 - b.1).abs() <= EPSILON
+
+}
+
+//Returns true if the segments cross or touch
+fn segments_touch(a: (f32, f32), b: (f32, f32), c: (f32, f32), d: (f32, f32)) -> bool
+{
+
+    let within = |p: (f32, f32), q: (f32, f32), r: (f32, f32)|
+    {
+
+        f32::min(p.0, q.0) - EPSILON <= r.0 && r.0 <= f32::max(p.0, q.0) + EPSILON &&
+        f32::min(p.1, q.1) - EPSILON <= r.1 && r.1 <= f32::max(p.1, q.1) + EPSILON
+
+    };
+
+    let (d1, d2) = (cross(c, d, a), cross(c, d, b));
+    let (d3, d4) = (cross(a, b, c), cross(a, b, d));
+
+    if ((d1 > EPSILON && d2 < -EPSILON) || (d1 < -EPSILON && d2 > EPSILON)) &&
+       ((d3 > EPSILON && d4 < -EPSILON) || (d3 < -EPSILON && d4 > EPSILON))
+    {
+
+        return true;
+
+    }
+
+    (d1.abs() <= EPSILON && within(c, d, a)) || (d2.abs() <= EPSILON && within(c, d, b)) ||
+    (d3.abs() <= EPSILON && within(a, b, c)) || (d4.abs() <= EPSILON && within(a, b, d))
+
+}
+
+fn self_intersects(points: &[(f32, f32)]) -> bool
+{
+
+    let count = points.len();
+    for i in 0..count
+    {
+
+        for j in (i + 1)..count
+        {
+
+            //Neighbouring edges always share a vertex
+            if j == i + 1 || (i == 0 && j == count - 1)
+            {
+
+                continue;
+
+            }
+
+            if segments_touch(points[i], points[(i + 1) % count], points[j], points[(j + 1) % count])
+            {
+
+                return true;
+
+            }
+
+        }
+
+    }
+
+    false
+
+}
+
+//Ear clipping of a counterclockwise polygon into counterclockwise triangles of indices
+fn triangulate(points: &[(f32, f32)]) -> Result<Vec<Vec<usize>>, DecomposeError>
+{
+
+    let mut ring: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::new();
+
+    while ring.len() > 3
+    {
+
+        let count = ring.len();
+        let mut clipped = false;
+
+        for i in 0..count
+        {
+
+            let (prev, current, next) = (ring[(i + count - 1) % count], ring[i], ring[(i + 1) % count]);
+            let (a, b, c) = (points[prev], points[current], points[next]);
+
+            let turn = cross(a, b, c);
+
+            //Collinear vertices add no area, so they are simply removed
+            if turn.abs() <= EPSILON
+            {
+
+                ring.remove(i);
+                clipped = true;
+                break;
+
+            }
+
+            if turn < 0.0
+            {
+
+                continue;
+
+            }
+
+            let blocked = ring.iter().any(|other|
+            {
+
+                let p = points[*other];
+
+                *other != prev && *other != current && *other != next &&
+                !same(p, a) && !same(p, b) && !same(p, c) &&
+                cross(a, b, p) >= -EPSILON && cross(b, c, p) >= -EPSILON && cross(c, a, p) >= -EPSILON
+
+            });
+
+            if !blocked
+            {
+
+                triangles.push(vec![prev, current, next]);
+                ring.remove(i);
+                clipped = true;
+                break;
+
+            }
+
+        }
+
+        //Only reachable through floating point error, where the remaining area would be lost
+        if !clipped
+        {
+
+            return Err(DecomposeError::Degenerate);
+
+        }
+
+    }
+
+    if ring.len() == 3 && cross(points[ring[0]], points[ring[1]], points[ring[2]]).abs() > EPSILON
+    {
+
+        triangles.push(ring);
+
+    }
+
+    Ok(triangles)
+
+}
+
+//Hertel-Mehlhorn: merges neighbouring pieces across their shared diagonal while the result is convex
+fn merge(mut pieces: Vec<Vec<usize>>, points: &[(f32, f32)]) -> Vec<Vec<usize>>
+{
+
+    let mut merged = true;
+    while merged
+    {
+
+        merged = false;
+
+        'search: for i in 0..pieces.len()
+        {
+
+            for j in (i + 1)..pieces.len()
+            {
+
+                if let Some(piece) = join(&pieces[i], &pieces[j])
+                {
+
+                    if is_convex(&piece, points)
+                    {
+
+                        pieces[i] = piece;
+                        pieces.remove(j);
+                        merged = true;
+                        break 'search;
+
+                    }
+
+                }
+
+            }
+
+        }
+
+    }
+
+    pieces
+
+}
+
+//Joins two counterclockwise pieces which share an edge, if they do
+fn join(left: &[usize], right: &[usize]) -> Option<Vec<usize>>
+{
+
+    for i in 0..left.len()
+    {
+
+        let (a, b) = (left[i], left[(i + 1) % left.len()]);
+
+        if let Some(j) = (0..right.len()).find(|j| right[*j] == b && right[(*j + 1) % right.len()] == a)
+        {
+
+            //Walk the left piece from b around to a, then the right piece from a around to b
+            let mut piece: Vec<usize> = (0..left.len()).map(|k| left[(i + 1 + k) % left.len()]).collect();
+            piece.extend((2..right.len()).map(|k| right[(j + k) % right.len()]));
+
+            return Some(piece);
+
+        }
+
+    }
+
+    None
+
+}
+
+fn is_convex(piece: &[usize], points: &[(f32, f32)]) -> bool
+{
+
+    let count = piece.len();
+    (0..count).all(|i| cross(points[piece[i]], points[piece[(i + 1) % count]], points[piece[(i + 2) % count]]) >= -EPSILON)
+
+}
+
+//Removes vertices which lie on the line between their neighbours
+fn simplify(vertices: Vec<(f32, f32)>) -> Vec<(f32, f32)>
+{
+
+    let count = vertices.len();
+    (0..count).filter(|i|
+    {
+
+        cross(vertices[(i + count - 1) % count], vertices[*i], vertices[(i + 1) % count]).abs() > EPSILON
+
+    }).map(|i| vertices[i]).collect()
+
+}
+
+#[cfg(test)]
+mod decompose_tests
+{
+
+    use super::*;
+
+    fn area(convex: &Convex) -> f32
+    {
+
+        match convex
+        {
+
+            Convex::Polygon(poly) => signed_area(&poly.vertices) / 2.0,
+            _ => 0.0
+
+        }
+
+    }
+
+    #[test]
+    fn test_convex_input()
+    {
+
+        let square = [(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)];
+        let pieces = decompose(&square).unwrap();
+
+        assert_eq!(pieces.len(), 1);
+        assert!((area(&pieces[0]) - 100.0).abs() < 0.001);
+
+    }
+
+    #[test]
+    fn test_concave()
+    {
+
+        //A comb with three teeth, given clockwise with a collinear vertex on the bottom edge
+        let comb = [(0.0, 0.0), (0.0, 20.0), (10.0, 20.0), (10.0, 10.0), (20.0, 10.0), (20.0, 20.0), (30.0, 20.0), (30.0, 10.0), (40.0, 10.0), (40.0, 20.0), (50.0, 20.0), (50.0, 0.0), (25.0, 0.0)];
+        let pieces = decompose(&comb).unwrap();
+
+        let total: f32 = pieces.iter().map(area).sum();
+        assert!((total - 800.0).abs() < 0.001);
+
+        for piece in pieces.iter()
+        {
+
+            if let Convex::Polygon(poly) = piece
+            {
+
+                assert!(poly.is_convex());
+                assert!(area(piece) > 0.0);
+
+            }
+
+        }
+
+        assert!(pieces.len() >= 3 && pieces.len() <= 8);
+
+    }
+
+    #[test]
+    fn test_errors()
+    {
+
+        assert_eq!(decompose(&[(0.0, 0.0), (1.0, 0.0), (1.0, 0.0)]).unwrap_err(), DecomposeError::TooFewVertices);
+        assert_eq!(decompose(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]).unwrap_err(), DecomposeError::Degenerate);
+
+        //A vertex touching the opposite edge
+        let pinched = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (5.0, 0.0), (0.0, 10.0)];
+        assert_eq!(decompose(&pinched).unwrap_err(), DecomposeError::SelfIntersecting);
+
+    }
+
+}
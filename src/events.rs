@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::components::CollisionRecord;
+
+/// An event sent by the [`SepaxPlugin`](crate::plugin::SepaxPlugin) when two entities begin
+/// or stop colliding. The first entity is always the [`Movable`](crate::components::Movable)
+/// one, and the second is the entity it collided with. When both are `Movable`, the lower
+/// `Entity` comes first, so the pair is the same on every frame.
+///
+/// `Started` is sent on the first frame that the pair collides, and `Stopped` is sent on the
+/// first frame that they no longer collide (including when one of them is despawned), so you
+/// can react to a collision once instead of checking every frame.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+///
+/// fn damage_system(mut events: EventReader<SepaxCollisionEvent>)
+/// {
+///
+///     for event in events.read()
+///     {
+///
+///         if let SepaxCollisionEvent::Started(movable, other) = event
+///         {
+///
+///             //Apply damage once per hit
+///
+///         }
+///
+///     }
+///
+/// }
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SepaxCollisionEvent
+{
+
+    Started(Entity, Entity),
+    Stopped(Entity, Entity)
+
+}
+
+/// An event sent by the [`SepaxPlugin`](crate::plugin::SepaxPlugin) for each collision that it
+/// resolves, on every frame that the entities collide. The record contains the contact points,
+/// which are useful for spawning impact particles, as well as the same information that is stored
+/// in the [`Movable`](crate::components::Movable) component of the `movable` entity.
+///
+/// Collisions between two `Movable` entities with a [`Mass`](crate::components::Mass) are sent once,
+/// from the point of view of the lower `Entity`.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct SepaxContactEvent
+{
+
+    pub movable: Entity,
+    pub record: CollisionRecord
+
+}
+
+/// The pairs of entities which collided during the current frame, along with those from the
+/// previous frame. This is filled in by the [`collision_system`](crate::plugin::collision_system)
+/// and compared by the [`collision_event_system`](crate::plugin::collision_event_system) in order
+/// to send [`SepaxCollisionEvent`]s.
+#[derive(Resource, Default)]
+pub struct CollisionPairs
+{
+
+    current: HashSet<(Entity, Entity)>,
+    previous: HashSet<(Entity, Entity)>,
+    contacts: Vec<SepaxContactEvent>
+
+}
+
+impl CollisionPairs
+{
+
+    /// Record that the movable entity collided with the other entity during this frame.
+    pub fn insert(&mut self, movable: Entity, other: Entity)
+    {
+
+        self.current.insert((movable, other));
+
+    }
+
+    /// Record that the movable entity collided with the other entity during this frame, along
+    /// with the record of the collision to be sent as a [`SepaxContactEvent`].
+    pub fn insert_contact(&mut self, movable: Entity, record: CollisionRecord)
+    {
+
+        self.insert(movable, record.other);
+        self.contacts.push(SepaxContactEvent { movable, record });
+
+    }
+
+    /// Takes the contacts recorded during this frame.
+    pub fn take_contacts(&mut self) -> Vec<SepaxContactEvent>
+    {
+
+        std::mem::take(&mut self.contacts)
+
+    }
+
+    /// Returns true if the two entities collided during the most recently finished frame, in either order.
+    pub fn contains(&self, first: Entity, second: Entity) -> bool
+    {
+
+        self.colliding().any(|pair| pair == (first, second) || pair == (second, first))
+
+    }
+
+    /// The pairs of entities which collided during the most recently finished frame.
+    pub fn colliding(&self) -> impl Iterator<Item = (Entity, Entity)> + '_
+    {
+
+        self.previous.iter().copied()
+
+    }
+
+    /// Finish the current frame, returning the events needed to describe the pairs which
+    /// started and stopped colliding since the last frame.
+    pub fn finish_frame(&mut self) -> Vec<SepaxCollisionEvent>
+    {
+
+        let mut started: Vec<(Entity, Entity)> = self.current.difference(&self.previous).copied().collect();
+        let mut stopped: Vec<(Entity, Entity)> = self.previous.difference(&self.current).copied().collect();
+
+        started.sort_unstable();
+        stopped.sort_unstable();
+
+        self.previous = std::mem::take(&mut self.current);
+
+        started.into_iter().map(|(a, b)| SepaxCollisionEvent::Started(a, b))
+        .chain(stopped.into_iter().map(|(a, b)| SepaxCollisionEvent::Stopped(a, b)))
+        .collect()
+
+    }
+
+}
@@ -0,0 +1,671 @@
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use sepax2d::prelude::*;
+
+use serde_json::Value;
+
+use std::path::Path;
+
+use crate::Convex;
+use crate::components::{read_fields, CollisionLayers, Sensor, Sepax};
+use crate::merge::merge_grid;
+
+/// The reasons that an LDtk project cannot be loaded.
+#[derive(Debug)]
+pub enum LdtkError
+{
+
+    /// A file could not be read.
+    Io(std::io::Error),
+
+    /// A file is not valid JSON.
+    Json(serde_json::Error),
+
+    /// A value that the importer needs is missing or has the wrong type, given as its name.
+    Invalid(String)
+
+}
+
+impl std::fmt::Display for LdtkError
+{
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+
+        match self
+        {
+
+            LdtkError::Io(error) => write!(f, "could not read file: {}", error),
+            LdtkError::Json(error) => write!(f, "invalid json: {}", error),
+            LdtkError::Invalid(name) => write!(f, "missing or invalid value {}", name)
+
+        }
+
+    }
+
+}
+
+impl std::error::Error for LdtkError {}
+
+impl From<std::io::Error> for LdtkError
+{
+
+    fn from(error: std::io::Error) -> Self
+    {
+
+        LdtkError::Io(error)
+
+    }
+
+}
+
+impl From<serde_json::Error> for LdtkError
+{
+
+    fn from(error: serde_json::Error) -> Self
+    {
+
+        LdtkError::Json(error)
+
+    }
+
+}
+
+/// How the cells of an IntGrid layer with a given value collide.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct IntGridCollider
+{
+
+    pub sensor: bool,
+    pub layers: Option<CollisionLayers>
+
+}
+
+/// Controls which parts of an LDtk project become colliders, and how.
+///
+/// By default, every non-zero cell of every IntGrid layer is a solid wall, and no entities are
+/// imported. Use [`with_int_grid`](LdtkSettings::with_int_grid) to choose which values collide
+/// and how, and [`with_entity`](LdtkSettings::with_entity) to import entities by their identifier.
+///
+/// The fields of imported entities can make them sensors or give them collision layers. A boolean
+/// field named `sensor` makes the entity a [`Sensor`](crate::components::Sensor), and integer
+/// fields named `memberships` and `filters` become its [`CollisionLayers`](crate::components::CollisionLayers),
+/// with a missing one treated as every layer. These names can be changed with the `with_*_field`
+/// methods. Enum, string or integer fields can also be mapped to layers with
+/// [`with_layers`](LdtkSettings::with_layers).
+///
+/// ```rust
+/// use bevy_sepax2d::prelude::*;
+/// use bevy_sepax2d::ldtk::{IntGridCollider, LdtkSettings};
+///
+/// const WALLS: u32 = 1 << 0;
+/// const WATER: u32 = 1 << 1;
+///
+/// let settings = LdtkSettings::default()
+/// .with_int_grid(1, IntGridCollider { sensor: false, layers: Some(CollisionLayers::new(WALLS, u32::MAX)) })
+/// .with_int_grid(2, IntGridCollider { sensor: true, layers: Some(CollisionLayers::new(WATER, u32::MAX)) })
+/// .with_entity("Door")
+/// .with_layers("Team", "Red", CollisionLayers::new(1 << 2, u32::MAX));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LdtkSettings
+{
+
+    /// The colliders for each IntGrid value. If empty, every non-zero value is a solid wall.
+    pub int_grid: HashMap<i64, IntGridCollider>,
+
+    /// The identifiers of the entities to import.
+    pub entities: HashSet<String>,
+
+    /// The name of the boolean field which makes an entity a sensor.
+    pub sensor_field: String,
+
+    /// The name of the integer field holding the layers that an entity belongs to.
+    pub memberships_field: String,
+
+    /// The name of the integer field holding the layers that an entity collides with.
+    pub filters_field: String,
+
+    /// The layers given to entities whose field, named by the first string, has the value in the
+    /// second string.
+    pub layer_fields: Vec<(String, String, CollisionLayers)>
+
+}
+
+impl Default for LdtkSettings
+{
+
+    fn default() -> Self
+    {
+
+        LdtkSettings
+        {
+
+            int_grid: HashMap::new(),
+            entities: HashSet::new(),
+            sensor_field: "sensor".to_string(),
+            memberships_field: "memberships".to_string(),
+            filters_field: "filters".to_string(),
+            layer_fields: Vec::new()
+
+        }
+
+    }
+
+}
+
+impl LdtkSettings
+{
+
+    /// Sets how the IntGrid cells with the given value collide. Once any value is set, cells with
+    /// values that have not been set are ignored.
+    pub fn with_int_grid(mut self, value: i64, collider: IntGridCollider) -> Self
+    {
+
+        self.int_grid.insert(value, collider);
+        self
+
+    }
+
+    /// Imports the entities with the given identifier as colliders the size of the entity.
+    /// Entities which are drawn as ellipses in LDtk become circles at their center with the average
+    /// of their radii, as in the Tiled importer, and the rest become AABBs.
+    pub fn with_entity(mut self, identifier: impl Into<String>) -> Self
+    {
+
+        self.entities.insert(identifier.into());
+        self
+
+    }
+
+    /// Sets the name of the boolean field which makes an entity a sensor.
+    pub fn with_sensor_field(mut self, field: impl Into<String>) -> Self
+    {
+
+        self.sensor_field = field.into();
+        self
+
+    }
+
+    /// Sets the name of the integer field holding the layers that an entity belongs to.
+    pub fn with_memberships_field(mut self, field: impl Into<String>) -> Self
+    {
+
+        self.memberships_field = field.into();
+        self
+
+    }
+
+    /// Sets the name of the integer field holding the layers that an entity collides with.
+    pub fn with_filters_field(mut self, field: impl Into<String>) -> Self
+    {
+
+        self.filters_field = field.into();
+        self
+
+    }
+
+    /// Gives the layers to entities whose field with the given name has the given value, such as
+    /// the identifier of an enum value.
+    pub fn with_layers(mut self, field: impl Into<String>, value: impl Into<String>, layers: CollisionLayers) -> Self
+    {
+
+        self.layer_fields.push((field.into(), value.into(), layers));
+        self
+
+    }
+
+    //The collider for the IntGrid value, if it collides
+    fn int_grid_collider(&self, value: i64) -> Option<IntGridCollider>
+    {
+
+        if self.int_grid.is_empty()
+        {
+
+            return (value != 0).then_some(IntGridCollider::default());
+
+        }
+
+        self.int_grid.get(&value).copied()
+
+    }
+
+}
+
+/// A collider imported from an LDtk project, placed in Bevy's coordinates.
+#[derive(Clone, Debug)]
+pub struct LdtkCollider
+{
+
+    /// The identifier of the level that the collider was found in.
+    pub level: String,
+
+    /// The identifier of the layer that the collider was found in.
+    pub layer: String,
+
+    /// The identifier of the entity, or of the IntGrid layer for merged cells.
+    pub identifier: String,
+
+    pub convex: Convex,
+    pub sensor: bool,
+    pub layers: Option<CollisionLayers>
+
+}
+
+impl LdtkCollider
+{
+
+    /// Spawns the collider as an immovable entity with a `Sepax`, a `Name`, and its `Sensor` and
+    /// `CollisionLayers` if it has them.
+    pub fn spawn(&self, commands: &mut Commands) -> Entity
+    {
+
+        let position = self.convex.shape().position();
+        let mut spawned = commands.spawn((Sepax { convex: self.convex.clone() }, Transform::from_xyz(position.0, position.1, 0.0), Name::new(self.identifier.clone())));
+
+        if self.sensor
+        {
+
+            spawned.insert(Sensor);
+
+        }
+
+        if let Some(layers) = self.layers
+        {
+
+            spawned.insert(layers);
+
+        }
+
+        spawned.id()
+
+    }
+
+}
+
+/// The colliders of an LDtk project, imported from its IntGrid layers and entities according to
+/// the [`LdtkSettings`](LdtkSettings).
+///
+/// The cells of each IntGrid layer which collide in the same way are merged into as few AABBs as
+/// possible using [`merge_grid`](crate::merge::merge_grid). Levels stored in separate `.ldtkl`
+/// files are loaded from the project's directory, and the levels of every world are read from
+/// projects with several worlds.
+///
+/// Positions are converted from LDtk's world coordinates, where y points down, by negating y, so
+/// the top left corner of a level at the origin of the LDtk world is at the origin in Bevy.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::ldtk::{LdtkProject, LdtkSettings};
+///
+/// fn level_system(mut commands: Commands)
+/// {
+///
+///     let project = LdtkProject::load("assets/world.ldtk", &LdtkSettings::default().with_entity("Platform")).unwrap();
+///     project.spawn(&mut commands);
+///
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LdtkProject
+{
+
+    pub colliders: Vec<LdtkCollider>
+
+}
+
+impl LdtkProject
+{
+
+    /// Loads an `.ldtk` project file, along with any separate level files that it uses.
+    pub fn load(path: impl AsRef<Path>, settings: &LdtkSettings) -> Result<LdtkProject, LdtkError>
+    {
+
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+
+        LdtkProject::parse(&text, path.parent().unwrap_or(Path::new("")), settings)
+
+    }
+
+    /// Parses the contents of an `.ldtk` project file. The paths of separate level files are
+    /// relative to the given directory, which is usually the one containing the project.
+    pub fn parse(text: &str, directory: &Path, settings: &LdtkSettings) -> Result<LdtkProject, LdtkError>
+    {
+
+        let root: Value = serde_json::from_str(text)?;
+
+        //Whether each entity is drawn as an ellipse
+        let ellipses: HashSet<&str> = root["defs"]["entities"].as_array().into_iter().flatten()
+        .filter(|entity| entity["renderMode"] == "Ellipse")
+        .filter_map(|entity| entity["identifier"].as_str())
+        .collect();
+
+        let mut project = LdtkProject::default();
+
+        //Projects with several worlds keep their levels in each world instead of the root
+        let worlds = root["worlds"].as_array().into_iter().flatten();
+        let levels = std::iter::once(array(&root, "levels")).chain(worlds.map(|world| array(world, "levels")));
+
+        for level in levels.collect::<Result<Vec<_>, LdtkError>>()?.into_iter().flatten()
+        {
+
+            //Separate level files contain the whole level
+            let external;
+            let level = match level["externalRelPath"].as_str()
+            {
+
+                Some(path) if level["layerInstances"].is_null() =>
+                {
+
+                    external = serde_json::from_str::<Value>(&std::fs::read_to_string(directory.join(path))?)?;
+                    &external
+
+                },
+                _ => level
+
+            };
+
+            project.read_level(level, settings, &ellipses)?;
+
+        }
+
+        Ok(project)
+
+    }
+
+    /// Spawns every collider with [`LdtkCollider::spawn`], returning the entities.
+    pub fn spawn(&self, commands: &mut Commands) -> Vec<Entity>
+    {
+
+        self.colliders.iter().map(|collider| collider.spawn(commands)).collect()
+
+    }
+
+    fn read_level(&mut self, level: &Value, settings: &LdtkSettings, ellipses: &HashSet<&str>) -> Result<(), LdtkError>
+    {
+
+        let name = string(level, "identifier")?;
+        let world = (number(level, "worldX")?, number(level, "worldY")?);
+
+        for layer in level["layerInstances"].as_array().into_iter().flatten()
+        {
+
+            let layer_name = string(layer, "__identifier")?;
+            let origin = (world.0 + number(layer, "__pxTotalOffsetX")?, world.1 + number(layer, "__pxTotalOffsetY")?);
+
+            match layer["__type"].as_str()
+            {
+
+                Some("IntGrid") => self.read_int_grid(layer, name, layer_name, origin, settings)?,
+                Some("Entities") => self.read_entities(layer, name, layer_name, origin, settings, ellipses)?,
+                _ => {}
+
+            }
+
+        }
+
+        Ok(())
+
+    }
+
+    fn read_int_grid(&mut self, layer: &Value, level: &str, name: &str, origin: (f32, f32), settings: &LdtkSettings) -> Result<(), LdtkError>
+    {
+
+        let width = number(layer, "__cWid")? as usize;
+        let height = number(layer, "__cHei")? as usize;
+        let size = number(layer, "__gridSize")?;
+
+        let cells: Vec<i64> = array(layer, "intGridCsv")?.iter().map(|cell| cell.as_i64().unwrap_or(0)).collect();
+
+        //Each way of colliding found in the layer, in order of their first cell
+        let mut colliders: Vec<IntGridCollider> = Vec::new();
+        for collider in cells.iter().filter_map(|value| settings.int_grid_collider(*value))
+        {
+
+            if !colliders.contains(&collider)
+            {
+
+                colliders.push(collider);
+
+            }
+
+        }
+
+        for collider in colliders
+        {
+
+            //LDtk stores the rows from the top, but the grid is merged from the bottom
+            let grid: Vec<bool> = (0..height).rev().flat_map(|row| (0..width).map(move |column| (row * width) + column))
+            .map(|index| cells.get(index).and_then(|value| settings.int_grid_collider(*value)) == Some(collider))
+            .collect();
+
+            let bottom = -(origin.1 + ((height as f32) * size));
+
+            for convex in merge_grid(&grid, width, (size, size), (origin.0, bottom))
+            {
+
+                self.colliders.push(LdtkCollider { level: level.to_string(), layer: name.to_string(), identifier: name.to_string(), convex, sensor: collider.sensor, layers: collider.layers });
+
+            }
+
+        }
+
+        Ok(())
+
+    }
+
+    fn read_entities(&mut self, layer: &Value, level: &str, name: &str, origin: (f32, f32), settings: &LdtkSettings, ellipses: &HashSet<&str>) -> Result<(), LdtkError>
+    {
+
+        for entity in array(layer, "entityInstances")?
+        {
+
+            let identifier = string(entity, "__identifier")?;
+
+            if !settings.entities.contains(identifier)
+            {
+
+                continue;
+
+            }
+
+            let (width, height) = (number(entity, "width")?, number(entity, "height")?);
+            let pivot = (entity["__pivot"][0].as_f64().unwrap_or(0.0) as f32, entity["__pivot"][1].as_f64().unwrap_or(0.0) as f32);
+            let (x, y) = (number(&entity["px"], 0)?, number(&entity["px"], 1)?);
+
+            //The top left corner of the entity in LDtk's world
+            let corner = (origin.0 + x - (pivot.0 * width), origin.1 + y - (pivot.1 * height));
+
+            let convex = if ellipses.contains(identifier)
+            {
+
+                Convex::Circle(Circle::new((corner.0 + (width / 2.0), -(corner.1 + (height / 2.0))), (width + height) / 4.0))
+
+            }
+            else
+            {
+
+                Convex::AABB(AABB::new((corner.0, -(corner.1 + height)), width, height))
+
+            };
+
+            //Each field's value as text, such as the identifier of an enum value
+            let fields: HashMap<&str, String> = entity["fieldInstances"].as_array().into_iter().flatten().filter_map(|field|
+            {
+
+                let value = match &field["__value"]
+                {
+
+                    Value::String(text) => text.clone(),
+                    Value::Null => return None,
+                    other => other.to_string()
+
+                };
+
+                Some((field["__identifier"].as_str()?, value))
+
+            }).collect();
+
+            let (sensor, mut layers) = read_fields(|field| fields.get(field).map(String::as_str), &settings.sensor_field, &settings.memberships_field, &settings.filters_field);
+
+            for (field, value, mapped) in settings.layer_fields.iter()
+            {
+
+                if fields.get(field.as_str()) == Some(value)
+                {
+
+                    layers = Some(*mapped);
+
+                }
+
+            }
+
+            self.colliders.push(LdtkCollider { level: level.to_string(), layer: name.to_string(), identifier: identifier.to_string(), convex, sensor, layers });
+
+        }
+
+        Ok(())
+
+    }
+
+}
+
+fn array<'a>(value: &'a Value, name: &str) -> Result<&'a Vec<Value>, LdtkError>
+{
+
+    value[name].as_array().ok_or_else(|| LdtkError::Invalid(name.to_string()))
+
+}
+
+fn string<'a>(value: &'a Value, name: &str) -> Result<&'a str, LdtkError>
+{
+
+    value[name].as_str().ok_or_else(|| LdtkError::Invalid(name.to_string()))
+
+}
+
+fn number<I: serde_json::value::Index + std::fmt::Display>(value: &Value, index: I) -> Result<f32, LdtkError>
+{
+
+    let name = index.to_string();
+    value[index].as_f64().map(|number| number as f32).ok_or(LdtkError::Invalid(name))
+
+}
+
+#[cfg(test)]
+mod ldtk_tests
+{
+
+    use super::*;
+
+    const PROJECT: &str = r#"{
+        "defs": { "entities": [ { "identifier": "Door", "renderMode": "Rectangle" }, { "identifier": "Coin", "renderMode": "Ellipse" } ] },
+        "levels": [ {
+            "identifier": "Level_0", "worldX": 100, "worldY": 0, "layerInstances": [
+            {
+                "__identifier": "Entities", "__type": "Entities", "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0,
+                "entityInstances": [
+                    { "__identifier": "Door", "__pivot": [0.5, 1], "px": [40, 32], "width": 16, "height": 32, "fieldInstances": [ { "__identifier": "Team", "__value": "Red" } ] },
+                    { "__identifier": "Coin", "__pivot": [0, 0], "px": [0, 0], "width": 8, "height": 12, "fieldInstances": [ { "__identifier": "sensor", "__value": true }, { "__identifier": "memberships", "__value": 4 } ] },
+                    { "__identifier": "Player", "__pivot": [0, 0], "px": [0, 0], "width": 8, "height": 8, "fieldInstances": [] }
+                ]
+            },
+            {
+                "__identifier": "Collisions", "__type": "IntGrid", "__cWid": 4, "__cHei": 3, "__gridSize": 8, "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0,
+                "intGridCsv": [ 0, 0, 0, 0,
+                                2, 2, 0, 0,
+                                1, 1, 1, 1 ]
+            } ]
+        } ]
+    }"#;
+
+    fn rect(convex: &Convex) -> ((f32, f32), f32, f32)
+    {
+
+        match convex
+        {
+
+            Convex::AABB(aabb) => (aabb.position, aabb.width, aabb.height),
+            _ => panic!("expected an AABB")
+
+        }
+
+    }
+
+    #[test]
+    fn test_int_grid()
+    {
+
+        let project = LdtkProject::parse(PROJECT, Path::new(""), &LdtkSettings::default()).unwrap();
+        let cells: Vec<&LdtkCollider> = project.colliders.iter().filter(|collider| collider.layer == "Collisions").collect();
+
+        //Every non-zero value is solid, and merged into one shape from the bottom row up
+        assert_eq!(cells.len(), 2);
+        assert_eq!(rect(&cells[0].convex), ((100.0, -24.0), 32.0, 8.0));
+        assert_eq!(rect(&cells[1].convex), ((100.0, -16.0), 16.0, 8.0));
+
+        let water = IntGridCollider { sensor: true, layers: Some(CollisionLayers::new(2, 2)) };
+        let project = LdtkProject::parse(PROJECT, Path::new(""), &LdtkSettings::default().with_int_grid(2, water)).unwrap();
+
+        assert_eq!(project.colliders.len(), 1);
+        assert!(project.colliders[0].sensor);
+        assert_eq!(project.colliders[0].layers, water.layers);
+
+    }
+
+    #[test]
+    fn test_entities()
+    {
+
+        let red = CollisionLayers::new(1, 1);
+        let settings = LdtkSettings::default().with_entity("Door").with_entity("Coin").with_layers("Team", "Red", red);
+
+        let project = LdtkProject::parse(PROJECT, Path::new(""), &settings).unwrap();
+        let entities: Vec<&LdtkCollider> = project.colliders.iter().filter(|collider| collider.layer == "Entities").collect();
+
+        assert_eq!(entities.len(), 2);
+
+        //The door's pivot is at the middle of its bottom edge
+        let door = entities[0];
+        assert_eq!(rect(&door.convex), ((132.0, -32.0), 16.0, 32.0));
+        assert_eq!((door.sensor, door.layers), (false, Some(red)));
+
+        let coin = entities[1];
+        let Convex::Circle(circle) = coin.convex else { panic!("expected a circle") };
+        //Ellipses become circles with the average of their radii
+        assert_eq!((circle.position, circle.radius), ((104.0, -6.0), 5.0));
+        assert_eq!((coin.sensor, coin.layers), (true, Some(CollisionLayers::new(4, u32::MAX))));
+
+        //Renamed fields are read instead of the defaults
+        let settings = LdtkSettings::default().with_entity("Coin").with_sensor_field("trigger").with_memberships_field("filters").with_filters_field("memberships");
+        let project = LdtkProject::parse(PROJECT, Path::new(""), &settings).unwrap();
+        let coin = project.colliders.iter().find(|collider| collider.identifier == "Coin").unwrap();
+
+        assert_eq!((coin.sensor, coin.layers), (false, Some(CollisionLayers::new(u32::MAX, 4))));
+
+        assert!(matches!(LdtkProject::parse("{}", Path::new(""), &settings), Err(LdtkError::Invalid(..))));
+
+    }
+
+    #[test]
+    fn test_worlds()
+    {
+
+        //The root levels are empty in projects with several worlds
+        let root: Value = serde_json::from_str(PROJECT).unwrap();
+        let level = root["levels"][0].clone();
+        let worlds = serde_json::json!({ "levels": [], "worlds": [ { "levels": [] }, { "levels": [level] } ] }).to_string();
+
+        let project = LdtkProject::parse(&worlds, Path::new(""), &LdtkSettings::default()).unwrap();
+
+        assert_eq!(project.colliders.len(), 2);
+        assert!(project.colliders.iter().all(|collider| collider.level == "Level_0"));
+
+        let invalid = serde_json::json!({ "levels": [], "worlds": [ {} ] }).to_string();
+        assert!(matches!(LdtkProject::parse(&invalid, Path::new(""), &LdtkSettings::default()), Err(LdtkError::Invalid(..))));
+
+    }
+
+}
@@ -91,6 +91,14 @@
 //! `debug` - Enables rendering of shapes.
 //! 
 //! `serde` - Enables (De)Serialization of Convex and Sepax types for easy loading.
+//! 
+//! `tiled` - Enables loading collision shapes from [Tiled](https://www.mapeditor.org/) `.tmx` maps and `.tsx` tilesets.
+//! 
+//! `ldtk` - Enables importing colliders from the IntGrid layers and entities of [LDtk](https://ldtk.io/) projects.
+//! 
+//! `asset` - Enables loading shapes as Bevy assets from `.collider.ron` files, with hot reloading.
+
+#![allow(clippy::type_complexity)]
 
 #[cfg(feature = "debug")]
 use bevy::prelude::*;
@@ -105,10 +113,37 @@ use sepax2d::prelude::*;
 
 pub mod plugin;
 pub mod components;
+pub mod broadphase;
+pub mod events;
+pub mod decompose;
+pub mod query;
+pub mod contact;
+pub mod merge;
+pub mod tilemap;
+
+#[cfg(feature = "tiled")]
+pub mod tiled;
+
+#[cfg(feature = "ldtk")]
+pub mod ldtk;
+
+#[cfg(feature = "asset")]
+pub mod asset;
 
 #[cfg(feature = "debug")]
 use components::Sepax;
 
+const SUPPORT_TOLERANCE: f32 = 0.0001;
+
+//Compares floats in tests, allowing for rounding error
+#[cfg(test)]
+pub(crate) fn float_equal(left: f32, right: f32) -> bool
+{
+
+    (left - right).abs() < 0.0001
+
+}
+
 /// An enum for the different types of shapes supported by sepax2d.
 /// For most use cases, you will store a `Convex` inside of a
 /// [`Sepax`](components::Sepax)
@@ -129,6 +164,237 @@ pub enum Convex
 
 }
 
+impl Convex
+{
+
+    /// Returns a copy of the shape which has been scaled and then rotated around its position,
+    /// by the angle with the given sine and cosine, before being moved to the given position.
+    /// This is used to place a local, untransformed shape in the world when syncing with a
+    /// `Transform`.
+    ///
+    /// Polygons, AABBs, and parallelograms are scaled exactly, including non-uniform and
+    /// negative scales. Capsules scale their arm exactly, and scale their radius by how much the
+    /// direction perpendicular to the arm is stretched. Circles scale their radius by the largest
+    /// absolute scale factor, so that they always contain the exactly scaled shape.
+    ///
+    /// `Circle`s are unaffected by rotation. An `AABB` cannot be rotated, so any rotation other
+    /// than a multiple of a half turn promotes it to a rectangular `Parallelogram`, which is
+    /// rotated around the AABB's position.
+    pub fn transformed(&self, position: (f32, f32), sin: f32, cos: f32, scale: (f32, f32)) -> Convex
+    {
+
+        let mut convex = match self
+        {
+
+            Convex::AABB(aabb) =>
+            {
+
+                let width = aabb.width * scale.0;
+                let height = aabb.height * scale.1;
+
+                if sin.abs() > f32::EPSILON
+                {
+
+                    let mut gram = Parallelogram::rectangle(aabb.position, width, height);
+                    gram.rotate_sincos(sin, cos);
+
+                    Convex::Parallelogram(gram)
+
+                }
+                else
+                {
+
+                    //Half a turn or a negative scale flips the box onto the other side of its
+                    //position, which is stored in its position to be offset below
+                    let (width, height) = if cos < 0.0 { (-width, -height) } else { (width, height) };
+
+                    Convex::AABB(AABB::new((f32::min(width, 0.0), f32::min(height, 0.0)), width.abs(), height.abs()))
+
+                }
+
+            },
+            Convex::Polygon(poly) =>
+            {
+
+                let mut poly = poly.clone();
+                for vertex in poly.vertices.iter_mut()
+                {
+
+                    *vertex = (vertex.0 * scale.0, vertex.1 * scale.1);
+
+                }
+
+                poly.rotate_sincos(sin, cos);
+
+                Convex::Polygon(poly)
+
+            },
+            Convex::Circle(circle) =>
+            {
+
+                let mut circle = *circle;
+                circle.radius *= f32::max(scale.0.abs(), scale.1.abs());
+
+                Convex::Circle(circle)
+
+            },
+            Convex::Parallelogram(gram) =>
+            {
+
+                let mut gram = *gram;
+                gram.u = (gram.u.0 * scale.0, gram.u.1 * scale.1);
+                gram.v = (gram.v.0 * scale.0, gram.v.1 * scale.1);
+                gram.rotate_sincos(sin, cos);
+
+                Convex::Parallelogram(gram)
+
+            },
+            Convex::Capsule(capsule) =>
+            {
+
+                let mut capsule = *capsule;
+
+                let arm = capsule.arm();
+                let length = f32::sqrt((arm.0 * arm.0) + (arm.1 * arm.1));
+                let stretch = if length > f32::EPSILON
+                {
+
+                    let perp = (-arm.1 * scale.0 / length, arm.0 * scale.1 / length);
+                    f32::sqrt((perp.0 * perp.0) + (perp.1 * perp.1))
+
+                }
+                else
+                {
+
+                    f32::max(scale.0.abs(), scale.1.abs())
+
+                };
+
+                capsule.set_arm((arm.0 * scale.0, arm.1 * scale.1));
+                capsule.set_radius(capsule.radius() * stretch);
+                capsule.rotate_sincos(sin, cos);
+
+                Convex::Capsule(capsule)
+
+            }
+
+        };
+
+        let offset = match &convex
+        {
+
+            Convex::AABB(aabb) => aabb.position,
+            _ => (0.0, 0.0)
+
+        };
+
+        convex.shape_mut().set_position((position.0 + offset.0, position.1 + offset.1));
+
+        convex
+
+    }
+
+    /// The point of the shape which is furthest in the given direction. When a whole side of the
+    /// shape is furthest, such as the bottom of an AABB when looking straight down, the midpoint of
+    /// that side is returned.
+    pub fn support(&self, direction: (f32, f32)) -> (f32, f32)
+    {
+
+        let length = f32::sqrt((direction.0 * direction.0) + (direction.1 * direction.1));
+        let direction = if length > f32::EPSILON { (direction.0 / length, direction.1 / length) } else { (0.0, 0.0) };
+
+        let furthest = |points: &[(f32, f32)]|
+        {
+
+            let dot = |point: &(f32, f32)| (point.0 * direction.0) + (point.1 * direction.1);
+            let max = points.iter().map(dot).fold(f32::NEG_INFINITY, f32::max);
+
+            let (sum, count) = points.iter()
+            .filter(|point| dot(point) >= max - SUPPORT_TOLERANCE)
+            .fold(((0.0, 0.0), 0.0), |(sum, count), point| ((sum.0 + point.0, sum.1 + point.1), count + 1.0));
+
+            (sum.0 / count, sum.1 / count)
+
+        };
+
+        match self
+        {
+
+            Convex::Polygon(_) | Convex::AABB(_) | Convex::Parallelogram(_) => furthest(&self.vertices()),
+            Convex::Circle(circle) => (circle.position.0 + (direction.0 * circle.radius), circle.position.1 + (direction.1 * circle.radius)),
+            Convex::Capsule(capsule) =>
+            {
+
+                let (position, arm, radius) = (capsule.position(), capsule.arm(), capsule.radius());
+                let center = furthest(&[(position.0 + arm.0, position.1 + arm.1), (position.0 - arm.0, position.1 - arm.1)]);
+
+                (center.0 + (direction.0 * radius), center.1 + (direction.1 * radius))
+
+            }
+
+        }
+
+    }
+
+    /// The corners of a polygon, AABB, or parallelogram in world space, in order around the shape.
+    /// Circles and capsules have no corners, so this is empty for them.
+    pub fn vertices(&self) -> Vec<(f32, f32)>
+    {
+
+        match self
+        {
+
+            Convex::Polygon(poly) => poly.vertices.iter().map(|(x, y)| (poly.position.0 + x, poly.position.1 + y)).collect(),
+            Convex::AABB(aabb) =>
+            {
+
+                let (x, y) = aabb.position;
+                vec![(x, y), (x + aabb.width, y), (x + aabb.width, y + aabb.height), (x, y + aabb.height)]
+
+            },
+            Convex::Parallelogram(gram) => gram.points().iter().map(|(x, y)| (gram.position.0 + x, gram.position.1 + y)).collect(),
+            Convex::Circle(_) | Convex::Capsule(_) => Vec::new()
+
+        }
+
+    }
+
+    /// A reference to the underlying shape.
+    pub fn shape(&self) -> &dyn Shape
+    {
+
+        match self
+        {
+
+            Convex::Polygon(poly) => poly,
+            Convex::Circle(circle) => circle,
+            Convex::AABB(aabb) => aabb,
+            Convex::Parallelogram(gram) => gram,
+            Convex::Capsule(capsule) => capsule
+
+        }
+
+    }
+
+    /// A mutable reference to the underlying shape.
+    pub fn shape_mut(&mut self) -> &mut dyn Shape
+    {
+
+        match self
+        {
+
+            Convex::Polygon(poly) => poly,
+            Convex::Circle(circle) => circle,
+            Convex::AABB(aabb) => aabb,
+            Convex::Parallelogram(gram) => gram,
+            Convex::Capsule(capsule) => capsule
+
+        }
+
+    }
+
+}
+
 /// A quick-and-dirty convenience method for spawning immovable shapes. Does not return
 /// any way to access the created entity, so it is only recommended for use in small
 /// projects or prototypes. Use 
@@ -154,7 +420,17 @@ pub mod prelude
 
     pub use crate::Convex;
 
-    pub use crate::plugin::SepaxPlugin;
-    pub use crate::components::{Sepax, NoCollision, Movable};
+    pub use crate::plugin::{SepaxBroadphasePlugin, SepaxPlugin, SepaxSettings};
+    pub use crate::broadphase::SpatialHash;
+    pub use crate::events::{SepaxCollisionEvent, SepaxContactEvent, CollisionPairs};
+    pub use crate::contact::ContactManifold;
+    pub use crate::merge::MergedCollider;
+    pub use crate::tilemap::{SepaxTilemap, TileCollider, TilemapCollider};
+    pub use crate::decompose::DecomposeError;
+    pub use crate::query::{SepaxQuery, SepaxFilter, RayHit};
+    pub use crate::components::{Sepax, SepaxCompound, SepaxLocal, SepaxOffset, NoCollision, Movable, CollisionRecord, CollisionLayers, Sensor, Mass, Ccd, Mergeable};
+
+    #[cfg(feature = "asset")]
+    pub use crate::asset::{SepaxAssetPlugin, SepaxColliderAsset, SepaxColliderHandle};
 
 }
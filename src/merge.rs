@@ -0,0 +1,399 @@
+use bevy::prelude::*;
+
+use sepax2d::prelude::*;
+
+use crate::Convex;
+use crate::components::{CollisionLayers, Mergeable, Movable, NoCollision, Sensor, Sepax};
+
+const EPSILON: f32 = 0.0001;
+
+/// Greedily merges the solid cells of a grid into rectangles which exactly cover them, such as
+/// the solid tiles of a level. The grid is stored row by row, starting from the bottom row, so the
+/// cell at column `x` and row `y` is `grid[(y * width) + x]`. Each cell is `tile_size` in size,
+/// and the bottom left corner of the grid is at `origin`.
+///
+/// Each rectangle is grown as far to the right as possible, and then as far upwards as the whole
+/// row allows, which produces few rectangles for the shapes found in typical levels.
+///
+/// ```rust
+/// use bevy_sepax2d::merge::merge_grid;
+///
+/// //An L shape of five tiles, the bottom row first
+/// let grid = [true, true, true,
+///             true, false, false,
+///             true, false, false];
+///
+/// assert_eq!(merge_grid(&grid, 3, (16.0, 16.0), (0.0, 0.0)).len(), 2);
+/// ```
+pub fn merge_grid(grid: &[bool], width: usize, tile_size: (f32, f32), origin: (f32, f32)) -> Vec<Convex>
+{
+
+    if width == 0
+    {
+
+        return Vec::new();
+
+    }
+
+    let height = grid.len() / width;
+    let mut used = vec![false; grid.len()];
+    let free = |used: &[bool], x: usize, y: usize| grid[(y * width) + x] && !used[(y * width) + x];
+
+    let mut merged = Vec::new();
+    for y in 0..height
+    {
+
+        for x in 0..width
+        {
+
+            if !free(&used, x, y)
+            {
+
+                continue;
+
+            }
+
+            let mut right = x + 1;
+            while right < width && free(&used, right, y)
+            {
+
+                right += 1;
+
+            }
+
+            let mut top = y + 1;
+            while top < height && (x..right).all(|column| free(&used, column, top))
+            {
+
+                top += 1;
+
+            }
+
+            for row in y..top
+            {
+
+                for column in x..right
+                {
+
+                    used[(row * width) + column] = true;
+
+                }
+
+            }
+
+            let position = (origin.0 + ((x as f32) * tile_size.0), origin.1 + ((y as f32) * tile_size.1));
+            merged.push(Convex::AABB(AABB::new(position, ((right - x) as f32) * tile_size.0, ((top - y) as f32) * tile_size.1)));
+
+        }
+
+    }
+
+    merged
+
+}
+
+/// Merges AABBs which line up into larger ones covering exactly the same area. Neighbours in the
+/// same row with the same height are joined first, and the resulting strips are then joined with
+/// those of the same width directly above or below them.
+///
+/// Unlike [`merge_grid`](merge_grid), the AABBs may be any size and anywhere, but only pairs whose
+/// edges line up are merged.
+///
+/// ```rust
+/// use bevy_sepax2d::merge::merge_aabbs;
+/// use sepax2d::prelude::*;
+///
+/// let tiles: Vec<AABB> = (0..4).map(|x| AABB::new(((x as f32) * 10.0, 0.0), 10.0, 10.0)).collect();
+/// let merged = merge_aabbs(&tiles);
+///
+/// assert_eq!(merged.len(), 1);
+/// assert_eq!(merged[0].width, 40.0);
+/// ```
+pub fn merge_aabbs(aabbs: &[AABB]) -> Vec<AABB>
+{
+
+    let rows = merge_runs(aabbs.to_vec(), |aabb| (aabb.position.1, aabb.height, aabb.position.0, aabb.width), |aabb, end| aabb.width = end - aabb.position.0);
+
+    merge_runs(rows, |aabb| (aabb.position.0, aabb.width, aabb.position.1, aabb.height), |aabb, end| aabb.height = end - aabb.position.1)
+
+}
+
+//Sorts the AABBs by their key of (start across, size across, start along, size along), then joins
+//neighbours with the same extent across which touch or overlap along the run
+fn merge_runs(mut aabbs: Vec<AABB>, key: impl Fn(&AABB) -> (f32, f32, f32, f32), extend: impl Fn(&mut AABB, f32)) -> Vec<AABB>
+{
+
+    aabbs.sort_by(|left, right|
+    {
+
+        let (left, right) = (key(left), key(right));
+        left.0.total_cmp(&right.0).then(left.1.total_cmp(&right.1)).then(left.2.total_cmp(&right.2))
+
+    });
+
+    let mut merged: Vec<AABB> = Vec::with_capacity(aabbs.len());
+    for aabb in aabbs
+    {
+
+        if let Some(last) = merged.last_mut()
+        {
+
+            let (start, size, along, length) = key(&aabb);
+            let (last_start, last_size, last_along, last_length) = key(last);
+
+            let end = last_along + last_length;
+
+            if (start - last_start).abs() <= EPSILON && (size - last_size).abs() <= EPSILON && along <= end + EPSILON
+            {
+
+                extend(last, f32::max(end, along + length));
+                continue;
+
+            }
+
+        }
+
+        merged.push(aabb);
+
+    }
+
+    merged
+
+}
+
+/// A component added to each entity spawned by the [`merge_system`](merge_system) to hold a
+/// merged shape.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MergedCollider;
+
+/// Merges the AABB shapes of immovable entities tagged [`Mergeable`](crate::components::Mergeable)
+/// into as few shapes as possible. Each merged shape is spawned as a new entity with a `Sepax`,
+/// the `Mergeable` and [`MergedCollider`](MergedCollider) components, and the
+/// [`CollisionLayers`](crate::components::CollisionLayers) and `Sensor` of the entities it came
+/// from. The original entities only lose their `Sepax`, so their sprites, other components, and
+/// children are kept.
+///
+/// Entities are only merged with others which have the same `CollisionLayers` and are either all
+/// sensors or all not. Entities with other shapes, or which are `Movable`, have `NoCollision`, or
+/// are the child of another entity, are left alone. When newly added tiles are merged with an
+/// earlier merged shape, the old `MergedCollider` entity is despawned.
+///
+/// This system is not added by the [`SepaxPlugin`](crate::plugin::SepaxPlugin). Add it after
+/// spawning your level, such as in `PostStartup`, or in `Update` to merge newly spawned tiles
+/// whenever a `Mergeable` entity is added:
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+/// use bevy_sepax2d::merge::merge_system;
+///
+/// App::new()
+/// .add_plugins(SepaxPlugin)
+/// .add_systems(PostStartup, merge_system);
+/// ```
+pub fn merge_system
+(
+    mut commands: Commands,
+    query: Query<(Entity, &Sepax, Option<&CollisionLayers>, Has<Sensor>, Has<MergedCollider>), (With<Mergeable>, Without<Movable>, Without<NoCollision>, Without<Parent>)>,
+    added: Query<(), Added<Mergeable>>
+)
+{
+
+    if added.is_empty()
+    {
+
+        return;
+
+    }
+
+    //The entities and shapes to merge, grouped by their layers and whether they are sensors
+    let mut groups: Vec<((Option<CollisionLayers>, bool), Vec<(Entity, AABB, bool)>)> = Vec::new();
+
+    let mut entities: Vec<_> = query.iter().collect();
+    entities.sort_by_key(|(entity, ..)| *entity);
+
+    for (entity, sepax, layers, sensor, merged) in entities
+    {
+
+        let Convex::AABB(aabb) = sepax.convex else { continue; };
+        let key = (layers.copied(), sensor);
+
+        match groups.iter_mut().find(|(group, _tiles)| *group == key)
+        {
+
+            Some((_key, tiles)) => tiles.push((entity, aabb, merged)),
+            None => groups.push((key, vec![(entity, aabb, merged)]))
+
+        }
+
+    }
+
+    for ((layers, sensor), tiles) in groups
+    {
+
+        let aabbs: Vec<AABB> = tiles.iter().map(|(_entity, aabb, _merged)| *aabb).collect();
+        let merged = merge_aabbs(&aabbs);
+
+        //Nothing to gain, such as when the group was already merged
+        if merged.len() == tiles.len()
+        {
+
+            continue;
+
+        }
+
+        for (entity, _aabb, merged) in tiles
+        {
+
+            //Only the entities spawned to hold earlier merged shapes are despawned
+            if merged
+            {
+
+                commands.entity(entity).despawn();
+
+            }
+            else
+            {
+
+                commands.entity(entity).remove::<Sepax>();
+
+            }
+
+        }
+
+        for aabb in merged
+        {
+
+            let mut spawned = commands.spawn((Sepax { convex: Convex::AABB(aabb) }, Mergeable, MergedCollider));
+
+            if let Some(layers) = layers
+            {
+
+                spawned.insert(layers);
+
+            }
+
+            if sensor
+            {
+
+                spawned.insert(Sensor);
+
+            }
+
+        }
+
+    }
+
+}
+
+#[cfg(test)]
+mod merge_tests
+{
+
+    use super::*;
+
+    //The position and size of an AABB
+    fn rect(aabb: &AABB) -> ((f32, f32), f32, f32)
+    {
+
+        (aabb.position, aabb.width, aabb.height)
+
+    }
+
+    fn rects(shapes: &[Convex]) -> Vec<((f32, f32), f32, f32)>
+    {
+
+        shapes.iter().filter_map(|convex| match convex { Convex::AABB(aabb) => Some(rect(aabb)), _ => None }).collect()
+
+    }
+
+    #[test]
+    fn test_merge_grid()
+    {
+
+        //A room with a floor, two walls and a ceiling, each ten tiles across, the bottom row first
+        let mut grid = vec![false; 100];
+        for i in 0..10
+        {
+
+            grid[i] = true;
+            grid[90 + i] = true;
+            grid[i * 10] = true;
+            grid[(i * 10) + 9] = true;
+
+        }
+
+        let merged = merge_grid(&grid, 10, (8.0, 8.0), (-40.0, -40.0));
+
+        let merged = rects(&merged);
+        let area: f32 = merged.iter().map(|(_position, width, height)| width * height).sum();
+
+        assert_eq!(merged.len(), 4);
+        assert_eq!(area, 36.0 * 64.0);
+        assert_eq!(merged[0], ((-40.0, -40.0), 80.0, 8.0));
+        assert!(merge_grid(&[], 0, (8.0, 8.0), (0.0, 0.0)).is_empty());
+
+    }
+
+    #[test]
+    fn test_merge_aabbs()
+    {
+
+        //A two by two block given out of order, with a tile of a different height beside it
+        let tiles = [AABB::new((10.0, 10.0), 10.0, 10.0), AABB::new((0.0, 0.0), 10.0, 10.0), AABB::new((0.0, 10.0), 10.0, 10.0), AABB::new((10.0, 0.0), 10.0, 10.0), AABB::new((20.0, 0.0), 10.0, 5.0)];
+        let merged: Vec<_> = merge_aabbs(&tiles).iter().map(rect).collect();
+
+        assert_eq!(merged.len(), 2);
+        assert!(merged.contains(&((0.0, 0.0), 20.0, 20.0)));
+        assert!(merged.contains(&((20.0, 0.0), 10.0, 5.0)));
+
+    }
+
+    #[test]
+    fn test_merge_system()
+    {
+
+        let mut app = App::new();
+        app.add_systems(Update, merge_system);
+
+        for x in 0..5
+        {
+
+            app.world.spawn((Sepax { convex: Convex::AABB(AABB::new(((x as f32) * 10.0, 0.0), 10.0, 10.0)) }, Mergeable));
+            app.world.spawn((Sepax { convex: Convex::AABB(AABB::new(((x as f32) * 10.0, 10.0), 10.0, 10.0)) }, Mergeable, CollisionLayers::new(0b10, 0b10)));
+
+        }
+
+        let circle = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Mergeable)).id();
+        let ghost = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((50.0, 0.0), 10.0, 10.0)) }, Mergeable, NoCollision)).id();
+        let tile = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-10.0, 0.0), 10.0, 10.0)) }, Mergeable, Name::new("Tile"))).id();
+
+        app.update();
+        app.update();
+
+        //The original keeps its other components
+        assert_eq!(app.world.get::<Name>(tile).map(|name| name.as_str()), Some("Tile"));
+        assert!(app.world.get::<Sepax>(tile).is_none());
+        assert!(app.world.get::<Sepax>(ghost).is_some());
+
+        let mut query = app.world.query_filtered::<(&Sepax, Option<&CollisionLayers>), With<MergedCollider>>();
+        let shapes: Vec<_> = query.iter(&app.world).map(|(sepax, layers)| (rects(std::slice::from_ref(&sepax.convex)), layers.copied())).collect();
+
+        assert_eq!(shapes.len(), 2);
+        assert!(shapes.contains(&(vec![((-10.0, 0.0), 60.0, 10.0)], None)));
+        assert!(shapes.contains(&(vec![((0.0, 10.0), 50.0, 10.0)], Some(CollisionLayers::new(0b10, 0b10)))));
+        assert!(app.world.get_entity(circle).is_some());
+
+        //A tile added later is merged with the earlier merged shape, which is replaced
+        app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-20.0, 0.0), 10.0, 10.0)) }, Mergeable));
+
+        app.update();
+
+        let shapes: Vec<_> = query.iter(&app.world).map(|(sepax, _layers)| rects(std::slice::from_ref(&sepax.convex))).collect();
+
+        assert_eq!(shapes.len(), 2);
+        assert!(shapes.contains(&vec![((-20.0, 0.0), 70.0, 10.0)]));
+
+    }
+
+}
@@ -3,29 +3,201 @@ use bevy::prelude::*;
 #[cfg(feature = "debug")]
 use bevy_prototype_lyon::prelude::*;
 
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::BTreeSet;
+
 use sepax2d::prelude::*;
 
-use crate::components::{Movable, NoCollision, Sepax};
+use crate::Convex;
+use crate::broadphase::{bounds_overlap, union_bounds, Bounds, SpatialHash};
+use crate::components::{Ccd, CollisionLayers, CollisionRecord, Mass, Movable, NoCollision, Sensor, Sepax, SepaxCompound, SepaxLocal, SepaxOffset};
+use crate::contact::contact_manifold;
+use crate::events::{CollisionPairs, SepaxCollisionEvent, SepaxContactEvent};
+use crate::query::{cast_convex, CAST_SKIN};
+use crate::tilemap::tilemap_system;
+
+//How far outside of a wall's face to look for a neighbouring wall when suppressing ghost edges
+const GHOST_PROBE: f32 = 0.01;
 
 /// A simple plugin which adds some basic functionality to your Bevy app!
 ///
 /// * Resets the collision information from the previous frame ([`clear_correction_system`](clear_correction_system))
-/// * Updates the location of any `Sepax` component attached to a 
-/// [`Transform`](https://docs.rs/bevy/latest/bevy/prelude/struct.Transform.html#impl-Default)
-/// ([`update_movable_system`](update_movable_system))
-/// * Provides inelastic collision between entities with a `Sepax` shape which are tagged 
-/// `Movable` and those that are not movable. ([`collision_system`](collision_system))
+/// * Updates the location of any `Sepax` component attached to a
+///   [`Transform`](https://docs.rs/bevy/latest/bevy/prelude/struct.Transform.html#impl-Default)
+///   ([`update_movable_system`](update_movable_system))
+/// * Updates the location of immovable `Sepax` components whose `Transform` has changed
+///   ([`update_static_system`](update_static_system))
+/// * Updates the location of any `Sepax` component on a child entity to match its `GlobalTransform`
+///   after transform propagation ([`update_global_system`](update_global_system))
+/// * Spawns and rebuilds the colliders of each [`SepaxTilemap`](crate::tilemap::SepaxTilemap)
+///   ([`tilemap_system`](crate::tilemap::tilemap_system))
+/// * Keeps the broadphase up to date, if enabled ([`broadphase_system`](broadphase_system))
+/// * Stops `Movable` entities with a [`Ccd`](crate::components::Ccd) component at the first
+///   immovable shape in their path ([`ccd_system`](ccd_system) and [`record_ccd_system`](record_ccd_system))
+/// * Pushes apart `Movable` entities which have a [`Mass`](crate::components::Mass), splitting
+///   the correction between them. ([`dynamic_collision_system`](dynamic_collision_system))
+/// * Provides inelastic collision between entities with a `Sepax` shape which are tagged
+///   `Movable` and those that are not movable. ([`collision_system`](collision_system))
+/// * Provides the same collisions for `Movable` child entities, applying the correction to the
+///   root of their hierarchy. ([`child_collision_system`](child_collision_system))
+/// * Sends a [`SepaxCollisionEvent`](crate::events::SepaxCollisionEvent) when a pair of entities
+///   starts or stops colliding, and a [`SepaxContactEvent`](crate::events::SepaxContactEvent) with
+///   the contact points of each resolved collision. ([`collision_event_system`](collision_event_system))
 ///
 /// Each of the above systems is public for you to manually add to your app if you want some but not all.
+///
+/// By default, every `Movable` is checked against every immovable shape. For levels with many
+/// immovable shapes, enable the broadphase with [`with_broadphase`](SepaxPlugin::with_broadphase),
+/// giving the cell size of its [`SpatialHash`](crate::broadphase::SpatialHash), which the plugin
+/// then keeps up to date. Inserting a `SpatialHash` resource yourself does the same.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+///
+/// App::new()
+/// .add_plugins(SepaxPlugin.with_broadphase(64.0));
+/// ```
+///
+/// A `Movable` which overlaps several immovable shapes at once, such as a player wedged into the
+/// corner between two tiles, is resolved over a number of solver iterations. More iterations give
+/// more accurate results in crowded areas at the cost of more collision checks. Insert a
+/// [`SepaxSettings`](SepaxSettings) resource to change them, otherwise the plugin inserts the
+/// default settings.
+#[derive(Clone, Copy, Debug, Default)]
 pub struct SepaxPlugin;
 
+impl SepaxPlugin
+{
+
+    /// The plugin with a spatial hash broadphase whose cells have the given size. Choose a cell
+    /// size around the size of your typical shape.
+    pub fn with_broadphase(self, cell_size: f32) -> SepaxBroadphasePlugin
+    {
+
+        SepaxBroadphasePlugin { cell_size }
+
+    }
+
+}
+
+/// The [`SepaxPlugin`](SepaxPlugin) with a [`SpatialHash`](crate::broadphase::SpatialHash)
+/// broadphase of the given cell size, created with
+/// [`SepaxPlugin::with_broadphase`](SepaxPlugin::with_broadphase). It adds the `SepaxPlugin`
+/// itself, so add one or the other.
+#[derive(Clone, Copy, Debug)]
+pub struct SepaxBroadphasePlugin
+{
+
+    /// The width and height of each cell of the spatial hash.
+    pub cell_size: f32
+
+}
+
+impl Plugin for SepaxBroadphasePlugin
+{
+
+    fn build(&self, app: &mut App)
+    {
+
+        app
+        .insert_resource(SpatialHash::new(self.cell_size))
+        .add_plugins(SepaxPlugin);
+
+    }
+
+}
+
+/// The settings used by the collision systems. The [`SepaxPlugin`](SepaxPlugin) inserts the
+/// default settings unless this resource is already present, and if the systems are added
+/// manually without it, the default settings are used.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+///
+/// App::new()
+/// .insert_resource(SepaxSettings::default().with_iterations(8))
+/// .add_plugins(SepaxPlugin);
+/// ```
+///
+/// Each solver iteration finds every immovable shape that a `Movable` overlaps, then resolves
+/// them from the deepest penetration to the shallowest, checking each again before it is resolved
+/// in case an earlier correction already separated them. Resolving the deepest first means that a
+/// shape resting across the seam between two tiles is pushed out of the tile that it sank into,
+/// rather than caught on the corner of its neighbour, and the result does not depend on the order
+/// that Bevy stores the entities in. Ties are broken by the order of the `Entity`.
+///
+/// Iterations stop early once nothing overlaps, so extra iterations only cost time when an
+/// entity is wedged between several shapes.
+///
+/// A floor made of many adjacent tiles has internal edges between them, which can push a shape
+/// sideways as it crosses a seam, as if it had hit a wall. When ghost edge suppression is enabled,
+/// a correction is checked by probing just outside of the contact point on the wall along the
+/// correction. If the probe lands inside another immovable shape that collides with the `Movable`,
+/// the face is internal, and the shallowest correction which does not face into a neighbour is
+/// used instead, so tiled floors behave like one continuous surface. This is disabled by default,
+/// as it changes how existing levels resolve.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct SepaxSettings
+{
+
+    /// The maximum number of solver iterations per `Movable` entity each frame.
+    pub iterations: usize,
+
+    /// Whether corrections which would push a shape into a neighbouring immovable shape are
+    /// replaced.
+    pub suppress_ghost_edges: bool
+
+}
+
+impl SepaxSettings
+{
+
+    /// Sets the number of solver iterations used to resolve each `Movable` entity. Values
+    /// below one are treated as one.
+    pub fn with_iterations(mut self, iterations: usize) -> Self
+    {
+
+        self.iterations = iterations;
+        self
+
+    }
+
+    /// Enables or disables ghost edge suppression, which is disabled by default.
+    pub fn with_ghost_edge_suppression(mut self, enabled: bool) -> Self
+    {
+
+        self.suppress_ghost_edges = enabled;
+        self
+
+    }
+
+}
+
+impl Default for SepaxSettings
+{
+
+    fn default() -> Self
+    {
+
+        SepaxSettings { iterations: 4, suppress_ghost_edges: false }
+
+    }
+
+}
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SepaxSystems
 {
 
     Clear,
     Update,
-    Collision
+    Broadphase,
+    Collision,
+    Events,
+    Global
 
 }
 
@@ -36,6 +208,10 @@ impl Plugin for SepaxPlugin
     {
 
         app
+        .add_event::<SepaxCollisionEvent>()
+        .add_event::<SepaxContactEvent>()
+        .init_resource::<CollisionPairs>()
+        .init_resource::<SepaxSettings>()
         .add_systems
         (
             PostUpdate,
@@ -50,6 +226,63 @@ impl Plugin for SepaxPlugin
             update_movable_system.after(clear_correction_system)
             .in_set(SepaxSystems::Update)
 
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            update_static_system
+            .in_set(SepaxSystems::Update)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            tilemap_system
+            .in_set(SepaxSystems::Update)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            broadphase_system
+            .run_if(resource_exists::<SpatialHash>())
+            .in_set(SepaxSystems::Broadphase)
+            .after(SepaxSystems::Update)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            ccd_system
+            .in_set(SepaxSystems::Collision)
+            .after(SepaxSystems::Broadphase)
+            .before(dynamic_collision_system)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            record_ccd_system
+            .in_set(SepaxSystems::Collision)
+            .after(dynamic_collision_system)
+            .after(collision_system)
+            .after(child_collision_system)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            dynamic_collision_system
+            .in_set(SepaxSystems::Collision)
+            .after(SepaxSystems::Broadphase)
+            .before(collision_system)
+
         )
         .add_systems
         (
@@ -57,9 +290,37 @@ impl Plugin for SepaxPlugin
             PostUpdate,
             collision_system
             .in_set(SepaxSystems::Collision)
-            .after(SepaxSystems::Update)
+            .after(SepaxSystems::Broadphase)
+            .before(bevy::transform::systems::propagate_transforms)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            child_collision_system
+            .in_set(SepaxSystems::Collision)
+            .after(collision_system)
             .before(bevy::transform::systems::propagate_transforms)
 
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            update_global_system
+            .in_set(SepaxSystems::Global)
+            .after(bevy::transform::TransformSystem::TransformPropagate)
+
+        )
+        .add_systems
+        (
+
+            PostUpdate,
+            collision_event_system
+            .in_set(SepaxSystems::Events)
+            .after(SepaxSystems::Collision)
+
         );
 
         #[cfg(feature = "debug")]
@@ -69,16 +330,16 @@ impl Plugin for SepaxPlugin
 
 }
 
-/// [`Movable`](crate::components::Movable) components store a list of axes
-/// that were used for collision resolution on the previous frame. This system
-/// resets that list each frame before the collision system generates new data.
+/// [`Movable`](crate::components::Movable) components store a record of each collision
+/// that was resolved on the previous frame. This system resets that list each frame
+/// before the collision system generates new data.
 pub fn clear_correction_system(mut query: Query<&mut Movable>)
 {
 
     for mut correction in query.iter_mut()
     {
 
-        correction.axes.clear();
+        correction.collisions.clear();
 
     }
 
@@ -86,57 +347,1608 @@ pub fn clear_correction_system(mut query: Query<&mut Movable>)
 
 /// Updates the position information contained inside of [`Sepax`](crate::components::Sepax)
 /// components to match the entity's translation in the world. This is necessary because
-/// sepax2d is not a Bevy-centric crate, so it does not use Transforms natively.
-pub fn update_movable_system(mut query: Query<(&Transform, &Movable, &mut Sepax)>)
+/// sepax2d is not a Bevy-centric crate, so it does not use Transforms natively. If the entity
+/// has a [`SepaxOffset`](crate::components::SepaxOffset), the shape is placed at that offset
+/// from the entity's translation instead.
+///
+/// Shapes are also scaled by the `Transform`'s scale and rotated by its rotation around the
+/// z-axis, using the untransformed copy of the shape stored in a
+/// [`SepaxLocal`](crate::components::SepaxLocal), which is captured the first time the
+/// entity is rotated or scaled. See [`Convex::transformed`](crate::Convex::transformed) for
+/// how each type of shape is affected. The parts of a
+/// [`SepaxCompound`](crate::components::SepaxCompound) are placed in the same way.
+pub fn update_movable_system
+(
+    mut commands: Commands,
+    mut query: Query<(Entity, &Transform, &Movable, &mut Sepax, Option<&SepaxLocal>, Option<&SepaxOffset>), Without<Parent>>,
+    mut compounds: Query<(&Transform, &mut SepaxCompound, Option<&SepaxOffset>), (With<Movable>, Without<Parent>)>
+)
 {
 
-    for (transform, _movable, mut sepax) in query.iter_mut()
+    for (entity, transform, _movable, mut sepax, local, offset) in query.iter_mut()
     {
 
-        let position = (transform.translation.x, transform.translation.y);
+        sync_transform(&mut commands, entity, transform, &mut sepax, local, offset);
+
+    }
+
+    for (transform, mut compound, offset) in compounds.iter_mut()
+    {
 
-        let shape = sepax.shape_mut();
-        shape.set_position(position);
+        sync_compound(transform, &mut compound, offset);
 
     }
 
 }
 
-/// Performs inelastic collisions between all [`Movable`](crate::components::Movable) and all immovable
-/// entities. If there is a collision, the normalized axis of resolution is stored inside the `Movable`
-/// component for use in your app. This points away from the immovable object. For example, if you are 
-/// making a platformer and want to check if the player has landed on something, you would check for
-/// axes with a positive y component. 
-pub fn collision_system(mut movable: Query<(&mut Movable, &mut Sepax, &mut Transform), Without<NoCollision>>, walls: Query<&Sepax, (Without<Movable>, Without<NoCollision>)>)
+/// Updates the shapes of immovable [`Sepax`](crate::components::Sepax) entities, such as moving
+/// platforms or sliding doors, to match their `Transform`. Only entities whose `Transform` or
+/// [`SepaxOffset`](crate::components::SepaxOffset) has changed since the last frame are updated,
+/// so static geometry costs nothing.
+///
+/// When the `Transform` of a `Sepax` is added, the shape's position relative to its translation is
+/// kept as a `SepaxOffset`, so existing level geometry keeps the position it was spawned with even
+/// when the `Transform` is elsewhere, such as at the centre of a sprite, and later moves along with
+/// the `Transform`. The shape is rotated and scaled about the translation straight away. Entities
+/// spawned with a `SepaxOffset` or a [`SepaxLocal`](crate::components::SepaxLocal) are placed at
+/// their `Transform` as usual.
+///
+/// Shapes are scaled and rotated in the same way as the [`update_movable_system`](update_movable_system).
+/// Immovable [`SepaxCompound`](crate::components::SepaxCompound)s are also updated when their
+/// parts change.
+pub fn update_static_system
+(
+    mut commands: Commands,
+    mut query: Query<(Entity, Ref<Transform>, &mut Sepax, Option<&SepaxLocal>, Option<&SepaxOffset>), (Without<Movable>, Without<Parent>, Or<(Changed<Transform>, Changed<SepaxOffset>)>)>,
+    mut compounds: Query<(&Transform, &mut SepaxCompound, Option<&SepaxOffset>), (Without<Movable>, Without<Parent>, Or<(Changed<Transform>, Changed<SepaxCompound>, Changed<SepaxOffset>)>)>
+)
 {
 
-    for (mut correct, mut sepax, mut transform) in movable.iter_mut()
+    for (entity, transform, mut sepax, local, offset) in query.iter_mut()
     {
 
-        for wall in walls.iter()
+        //Shapes are spawned in the world, so their position relative to a new Transform is kept
+        let spawned;
+        let offset = match offset
         {
 
-            let shape = sepax.shape_mut();
-            let correction = sat_collision(wall.shape(), shape);
+            None if transform.is_added() && local.is_none() =>
+            {
 
-            let old_position = shape.position();
-            let new_position = (old_position.0 + correction.0, old_position.1 + correction.1);
+                let position = sepax.shape().position();
+                spawned = SepaxOffset((position.0 - transform.translation.x, position.1 - transform.translation.y));
 
-            shape.set_position(new_position);
-            transform.translation.x = new_position.0;
-            transform.translation.y = new_position.1;
+                if spawned.0 != (0.0, 0.0)
+                {
 
-            let length = f32::sqrt((correction.0 * correction.0) + (correction.1 * correction.1));
+                    commands.entity(entity).insert(spawned);
 
-            if length > f32::EPSILON
-            {
+                }
 
-                correct.axes.push((correction.0 / length, correction.1 / length));
+                Some(&spawned)
 
-            }
+            },
+            offset => offset
+
+        };
+
+        sync_transform(&mut commands, entity, &transform, &mut sepax, local, offset);
+
+    }
+
+    for (transform, mut compound, offset) in compounds.iter_mut()
+    {
+
+        sync_compound(transform, &mut compound, offset);
+
+    }
+
+}
+
+/// Updates the shapes of [`Sepax`](crate::components::Sepax) and
+/// [`SepaxCompound`](crate::components::SepaxCompound) components on child entities to match
+/// their `GlobalTransform`, so that a shape attached to a child entity collides where the child
+/// actually is in the world rather than relative to its parent. This runs after transform
+/// propagation and only updates shapes whose `GlobalTransform` has changed.
+///
+/// Shapes are scaled and rotated in the same way as the [`update_movable_system`](update_movable_system).
+pub fn update_global_system
+(
+    mut commands: Commands,
+    mut query: Query<(Entity, &GlobalTransform, &mut Sepax, Option<&SepaxLocal>, Option<&SepaxOffset>), (With<Parent>, Or<(Changed<GlobalTransform>, Changed<SepaxOffset>)>)>,
+    mut compounds: Query<(&GlobalTransform, &mut SepaxCompound, Option<&SepaxOffset>), (With<Parent>, Or<(Changed<GlobalTransform>, Changed<SepaxCompound>, Changed<SepaxOffset>)>)>
+)
+{
+
+    for (entity, global, mut sepax, local, offset) in query.iter_mut()
+    {
+
+        sync_transform(&mut commands, entity, &global.compute_transform(), &mut sepax, local, offset);
+
+    }
+
+    for (global, mut compound, offset) in compounds.iter_mut()
+    {
+
+        sync_compound(&global.compute_transform(), &mut compound, offset);
+
+    }
+
+}
+
+//The position, rotation, and scale to place a local shape with, including the offset
+fn placement(transform: &Transform, offset: Option<&SepaxOffset>) -> ((f32, f32), f32, f32, (f32, f32))
+{
+
+    //The rotation around the z-axis, found from where it sends the x-axis
+    let axis = transform.rotation * Vec3::X;
+    let length = f32::sqrt((axis.x * axis.x) + (axis.y * axis.y));
+    let (sin, cos) = if length > f32::EPSILON { (axis.y / length, axis.x / length) } else { (0.0, 1.0) };
+
+    let scale = (transform.scale.x, transform.scale.y);
+
+    //The offset is in the entity's local space, so it is scaled and rotated along with the shape
+    let offset = offset.map_or((0.0, 0.0), |offset| (offset.0.0 * scale.0, offset.0.1 * scale.1));
+    let offset = sepax2d::rotate!(sin, cos, offset);
+
+    let position = (transform.translation.x + offset.0, transform.translation.y + offset.1);
+
+    (position, sin, cos, scale)
+
+}
+
+//Places the shape at the transform, rebuilding it from the local copy if it has been rotated or scaled
+fn sync_transform(commands: &mut Commands, entity: Entity, transform: &Transform, sepax: &mut Sepax, local: Option<&SepaxLocal>, offset: Option<&SepaxOffset>)
+{
+
+    let (position, sin, cos, scale) = placement(transform, offset);
+    let scaled = (scale.0 - 1.0).abs() > f32::EPSILON || (scale.1 - 1.0).abs() > f32::EPSILON;
+
+    if let Some(local) = local
+    {
+
+        sepax.convex = local.convex.transformed(position, sin, cos, scale);
+
+    }
+    else if sin.abs() > f32::EPSILON || cos < 0.0 || scaled
+    {
+
+        let mut convex = sepax.convex.clone();
+        convex.shape_mut().set_position((0.0, 0.0));
+
+        sepax.convex = convex.transformed(position, sin, cos, scale);
+        commands.entity(entity).insert(SepaxLocal { convex });
+
+    }
+    else
+    {
+
+        sepax.shape_mut().set_position(position);
+
+    }
+
+}
+
+//Places the parts of the compound at the transform
+fn sync_compound(transform: &Transform, compound: &mut SepaxCompound, offset: Option<&SepaxOffset>)
+{
+
+    let (position, sin, cos, scale) = placement(transform, offset);
+    compound.transform(position, sin, cos, scale);
+
+}
+
+/// Keeps the [`SpatialHash`](crate::broadphase::SpatialHash) in sync with the immovable
+/// [`Sepax`](crate::components::Sepax) and [`SepaxCompound`](crate::components::SepaxCompound)
+/// entities in the world. Shapes are only re-inserted when their component changes, so static
+/// geometry costs nothing once it has been inserted. When the resource is first inserted, every
+/// existing immovable shape is added to it.
+pub fn broadphase_system
+(
+    mut hash: ResMut<SpatialHash>,
+    changed: Query<(Entity, AnyOf<(&Sepax, &SepaxCompound)>), (Without<Movable>, Or<(Changed<Sepax>, Changed<SepaxCompound>)>)>,
+    statics: Query<(Entity, AnyOf<(&Sepax, &SepaxCompound)>), Without<Movable>>,
+    added_movable: Query<Entity, Added<Movable>>,
+    mut removed_sepax: RemovedComponents<Sepax>,
+    mut removed_compound: RemovedComponents<SepaxCompound>,
+    mut removed_movable: RemovedComponents<Movable>
+)
+{
+
+    if hash.is_added()
+    {
+
+        for (entity, shapes) in statics.iter()
+        {
+
+            hash.insert(entity, union_bounds(convexes(shapes)));
+
+        }
+
+    }
+
+    for entity in removed_sepax.read().chain(removed_compound.read())
+    {
+
+        hash.remove(entity);
+
+    }
+
+    for entity in added_movable.iter()
+    {
+
+        hash.remove(entity);
+
+    }
+
+    for entity in removed_movable.read()
+    {
+
+        if let Ok((entity, shapes)) = statics.get(entity)
+        {
+
+            hash.insert(entity, union_bounds(convexes(shapes)));
 
         }
 
     }
 
-}
\ No newline at end of file
+    for (entity, shapes) in changed.iter()
+    {
+
+        hash.insert(entity, union_bounds(convexes(shapes)));
+
+    }
+
+}
+
+/// Performs inelastic collisions between all [`Movable`](crate::components::Movable) and all immovable
+/// entities. If there is a collision, a [`CollisionRecord`](crate::components::CollisionRecord) is stored
+/// inside the `Movable` component for use in your app, containing the entity that was collided with, the
+/// penetration depth, an approximate contact point, and the normalized axis of resolution. This points away
+/// from the immovable object. For example, if you are making a platformer and want to check if the player
+/// has landed on something, you would check for axes with a positive y component.
+///
+/// Pairs of entities whose [`CollisionLayers`](crate::components::CollisionLayers) do not
+/// interact are skipped. If either entity is a [`Sensor`](crate::components::Sensor), then the
+/// pair is only checked for overlap and is never pushed apart. Each colliding pair is recorded in the
+/// [`CollisionPairs`](crate::events::CollisionPairs) resource, if it is present, so add it with
+/// `init_resource` when adding the systems without the plugin in order to receive events.
+///
+/// Overlapping immovable shapes are resolved from the deepest penetration to the shallowest, over the
+/// number of iterations in the [`SepaxSettings`](SepaxSettings) resource, which also controls whether
+/// ghost edges between neighbouring shapes are suppressed. When the
+/// [`SpatialHash`](crate::broadphase::SpatialHash) resource is present, only the shapes in
+/// nearby cells are checked, which produces exactly the same result as checking all of them.
+///
+/// Every part of a [`SepaxCompound`](crate::components::SepaxCompound) is resolved against every
+/// part of each immovable entity, moving the whole compound each time, and the corrections are
+/// merged into a single change of the entity's `Transform`.
+pub fn collision_system
+(
+    mut movable: Query<(Entity, &mut Movable, AnyOf<(&mut Sepax, &mut SepaxCompound)>, &mut Transform, Option<&CollisionLayers>, Has<Sensor>), (Without<NoCollision>, Without<Parent>)>,
+    walls: WallQuery,
+    broadphase: Option<Res<SpatialHash>>,
+    settings: Option<Res<SepaxSettings>>,
+    mut pairs: Option<ResMut<CollisionPairs>>
+)
+{
+
+    let walls = Walls::new(&walls, broadphase.as_deref(), settings.as_deref());
+
+    for (moving, mut correct, (mut sepax, mut compound), mut transform, layers, sensor) in movable.iter_mut()
+    {
+
+        let correction = walls.resolve(moving, convexes_mut(&mut sepax, &mut compound), &mut correct, layers, sensor, pairs.as_deref_mut());
+
+        if correction != (0.0, 0.0)
+        {
+
+            transform.translation.x += correction.0;
+            transform.translation.y += correction.1;
+
+        }
+
+    }
+
+}
+
+/// Performs inelastic collisions between immovable entities and [`Movable`](crate::components::Movable)
+/// entities which are children of another entity, such as a hitbox attached to a player. The
+/// correction is applied to the `Transform` of the root entity of the hierarchy, which must also be
+/// `Movable`, and the collision records are stored in both `Movable` components. A child whose root
+/// is not `Movable` is not resolved, and a warning is logged the first time that it is skipped.
+///
+/// The shapes of child entities are synced from their `GlobalTransform` after transform propagation
+/// by the [`update_global_system`](update_global_system). Any movement of the root entity since then
+/// is taken into account, but changes to its rotation or scale are not seen until the next frame.
+#[allow(clippy::too_many_arguments)]
+pub fn child_collision_system
+(
+    mut children: Query<(Entity, &mut Movable, AnyOf<(&mut Sepax, &mut SepaxCompound)>, Option<&CollisionLayers>, Has<Sensor>), (With<Parent>, Without<NoCollision>)>,
+    mut roots: Query<(&mut Transform, &GlobalTransform, &mut Movable, Option<&mut Sepax>, Option<&mut SepaxCompound>), Without<Parent>>,
+    parents: Query<&Parent>,
+    walls: WallQuery,
+    broadphase: Option<Res<SpatialHash>>,
+    settings: Option<Res<SepaxSettings>>,
+    mut pairs: Option<ResMut<CollisionPairs>>,
+    mut skipped: Local<BTreeSet<Entity>>
+)
+{
+
+    let walls = Walls::new(&walls, broadphase.as_deref(), settings.as_deref());
+
+    for (child, mut correct, (mut sepax, mut compound), layers, sensor) in children.iter_mut()
+    {
+
+        let root = parents.iter_ancestors(child).last().unwrap_or(child);
+        let Ok((mut transform, global, mut root_correct, mut root_sepax, mut root_compound)) = roots.get_mut(root) else
+        {
+
+            if skipped.insert(child)
+            {
+
+                warn!("Movable child {:?} is not resolved because the root of its hierarchy, {:?}, is not Movable", child, root);
+
+            }
+
+            continue;
+
+        };
+
+        let shapes = convexes_mut(&mut sepax, &mut compound);
+
+        //The shape was synced with the root where it was during the last propagation
+        let moved = transform.translation - global.translation();
+        if moved.x != 0.0 || moved.y != 0.0
+        {
+
+            translate(shapes, (moved.x, moved.y));
+
+        }
+
+        let start = correct.collisions.len();
+        let correction = walls.resolve(child, shapes, &mut correct, layers, sensor, pairs.as_deref_mut());
+
+        if correction != (0.0, 0.0)
+        {
+
+            transform.translation.x += correction.0;
+            transform.translation.y += correction.1;
+
+            translate(convexes_mut(&mut root_sepax, &mut root_compound), correction);
+
+            root_correct.collisions.extend_from_slice(&correct.collisions[start..]);
+
+        }
+
+    }
+
+}
+
+/// Performs inelastic collisions between every pair of [`Movable`](crate::components::Movable)
+/// entities which both have a [`Mass`](crate::components::Mass). The correction is split between
+/// the two entities according to their inverse masses, and each entity stores a collision record with
+/// the normalized axis pointing away from the other in its `Movable` component.
+///
+/// A pair of `Movable` entities where either one is a [`Sensor`](crate::components::Sensor) is
+/// recorded as colliding whenever they overlap, whether or not they have a `Mass`, so a sensor
+/// such as a bullet can detect the `Movable` entities that it hits without pushing them.
+///
+/// This runs before the [`collision_system`](collision_system), so that immovable shapes have the
+/// final say and entities are never pushed into walls by each other. Pairs are filtered by their
+/// [`CollisionLayers`](crate::components::CollisionLayers) in the same way as the `collision_system`,
+/// and the parts of a [`SepaxCompound`](crate::components::SepaxCompound) are resolved one pair at
+/// a time. Child entities are not included.
+pub fn dynamic_collision_system
+(
+    mut bodies: Query<(Entity, &mut Movable, AnyOf<(&mut Sepax, &mut SepaxCompound)>, &mut Transform, Option<&Mass>, Option<&CollisionLayers>, Has<Sensor>), (Without<NoCollision>, Without<Parent>)>,
+    mut pairs: Option<ResMut<CollisionPairs>>
+)
+{
+
+    let mut combinations = bodies.iter_combinations_mut();
+    while let Some([first, second]) = combinations.fetch_next()
+    {
+
+        let (first_entity, mut first_correct, (mut first_sepax, mut first_compound), mut first_transform, first_mass, first_layers, first_sensor) = first;
+        let (second_entity, mut second_correct, (mut second_sepax, mut second_compound), mut second_transform, second_mass, second_layers, second_sensor) = second;
+
+        let first_shapes = convexes_mut(&mut first_sepax, &mut first_compound);
+        let second_shapes = convexes_mut(&mut second_sepax, &mut second_compound);
+
+        if !CollisionLayers::compatible(first_layers, second_layers) || !bounds_overlap(union_bounds(first_shapes), union_bounds(second_shapes))
+        {
+
+            continue;
+
+        }
+
+        //The order of the combinations changes whenever either entity changes archetype, so the
+        //pair is recorded with the lower Entity first to keep it the same across frames
+        let pair = (first_entity.min(second_entity), first_entity.max(second_entity));
+
+        if first_sensor || second_sensor
+        {
+
+            if overlaps(first_shapes, second_shapes)
+            {
+
+                if let Some(pairs) = pairs.as_deref_mut()
+                {
+
+                    pairs.insert(pair.0, pair.1);
+
+                }
+
+            }
+
+            continue;
+
+        }
+
+        let (Some(first_mass), Some(second_mass)) = (first_mass, second_mass) else { continue; };
+
+        let first_inverse = first_mass.inverse();
+        let second_inverse = second_mass.inverse();
+        let total = first_inverse + second_inverse;
+
+        if total <= f32::EPSILON
+        {
+
+            continue;
+
+        }
+
+        let first_share = -first_inverse / total;
+        let second_share = second_inverse / total;
+
+        let mut collided = false;
+        for i in 0..first_shapes.len()
+        {
+
+            for j in 0..second_shapes.len()
+            {
+
+                let correction = sat_collision(first_shapes[i].shape(), second_shapes[j].shape());
+                let length = f32::sqrt((correction.0 * correction.0) + (correction.1 * correction.1));
+
+                if length <= f32::EPSILON
+                {
+
+                    continue;
+
+                }
+
+                shift(first_shapes, &mut first_transform, (correction.0 * first_share, correction.1 * first_share));
+                shift(second_shapes, &mut second_transform, (correction.0 * second_share, correction.1 * second_share));
+
+                let normal = (correction.0 / length, correction.1 / length);
+                let contacts = contact_manifold(&first_shapes[i], &second_shapes[j], normal);
+
+                let first_record = CollisionRecord::new(second_entity, (-normal.0, -normal.1), length, contacts);
+                let second_record = CollisionRecord::new(first_entity, normal, length, contacts);
+
+                first_correct.collisions.push(first_record);
+                second_correct.collisions.push(second_record);
+                if let Some(pairs) = pairs.as_deref_mut()
+                {
+
+                    let (movable, record) = if pair.0 == first_entity { (first_entity, first_record) } else { (second_entity, second_record) };
+                    pairs.insert_contact(movable, record);
+
+                }
+
+                collided = true;
+
+            }
+
+        }
+
+        if collided
+        {
+
+            if let Some(pairs) = pairs.as_deref_mut()
+            {
+
+                pairs.insert(pair.0, pair.1);
+
+            }
+
+        }
+
+    }
+
+}
+
+/// Prevents fast [`Movable`](crate::components::Movable) entities with a [`Ccd`](crate::components::Ccd)
+/// component from passing through immovable shapes in a single frame. The shape is swept from the
+/// position recorded at the end of the previous frame to its current position, and if it hits an
+/// immovable shape on the way, the entity is moved back to just before the impact. The impact is
+/// stored as a collision record with zero depth in the `Movable` component and the pair is
+/// recorded as colliding.
+///
+/// Only the movement of the shape is swept, not changes to its rotation or scale. This runs before
+/// the [`collision_system`](collision_system), which resolves any remaining overlap as usual.
+/// Sensors are never swept or swept against, and pairs are filtered by their
+/// [`CollisionLayers`](crate::components::CollisionLayers).
+pub fn ccd_system
+(
+    mut movable: Query<(Entity, &mut Movable, AnyOf<(&mut Sepax, &mut SepaxCompound)>, &mut Transform, Ref<Ccd>, Option<&CollisionLayers>, Has<Sensor>), (Without<NoCollision>, Without<Parent>)>,
+    walls: WallQuery,
+    broadphase: Option<Res<SpatialHash>>,
+    mut pairs: Option<ResMut<CollisionPairs>>
+)
+{
+
+    let walls = Walls::new(&walls, broadphase.as_deref(), None);
+
+    for (moving, mut correct, (mut sepax, mut compound), mut transform, ccd, layers, sensor) in movable.iter_mut()
+    {
+
+        let Some(previous) = ccd.previous else { continue; };
+
+        let shapes = convexes_mut(&mut sepax, &mut compound);
+        let Some(first) = shapes.first() else { continue; };
+
+        let current = first.shape().position();
+        let motion = (current.0 - previous.0, current.1 - previous.1);
+        let length = f32::sqrt((motion.0 * motion.0) + (motion.1 * motion.1));
+
+        if sensor || ccd.is_added() || length <= f32::EPSILON
+        {
+
+            continue;
+
+        }
+
+        let mut start = shapes.to_vec();
+        translate(&mut start, (-motion.0, -motion.1));
+
+        let swept = union_bounds(&start);
+        let swept = ((swept.0.0 + f32::min(motion.0, 0.0), swept.0.1 + f32::min(motion.1, 0.0)), (swept.1.0 + f32::max(motion.0, 0.0), swept.1.1 + f32::max(motion.1, 0.0)));
+
+        let mut earliest: Option<(Entity, usize, Convex, f32, (f32, f32))> = None;
+        for entity in walls.candidates(swept).iter().copied()
+        {
+
+            let Ok((_entity, wall, wall_layers, wall_sensor)) = walls.query.get(entity) else { continue; };
+
+            if wall_sensor || !CollisionLayers::compatible(layers, wall_layers)
+            {
+
+                continue;
+
+            }
+
+            for (index, part) in start.iter().enumerate()
+            {
+
+                for target in convexes(wall).iter()
+                {
+
+                    //Shapes which already overlapped are left to the collision system
+                    if let Some((toi, normal)) = cast_convex(part, motion, target)
+                    {
+
+                        if toi > 0.0 && toi < 1.0 && !earliest.as_ref().is_some_and(|(_entity, _index, _target, best, _normal)| *best <= toi)
+                        {
+
+                            earliest = Some((entity, index, target.clone(), toi, normal));
+
+                        }
+
+                    }
+
+                }
+
+            }
+
+        }
+
+        if let Some((entity, index, target, toi, normal)) = earliest
+        {
+
+            let safe = f32::max(toi - (CAST_SKIN / length), 0.0);
+            let correction = (motion.0 * (safe - 1.0), motion.1 * (safe - 1.0));
+
+            shift(shapes, &mut transform, correction);
+
+            let record = CollisionRecord::new(entity, normal, 0.0, contact_manifold(&target, &shapes[index], normal));
+
+            correct.collisions.push(record);
+            if let Some(pairs) = pairs.as_deref_mut()
+            {
+
+                pairs.insert_contact(moving, record);
+
+            }
+
+        }
+
+    }
+
+}
+
+/// Records the position of each [`Ccd`](crate::components::Ccd) entity's shape once collisions
+/// have been resolved, including those of its children, to be swept from on the next frame by the
+/// [`ccd_system`](ccd_system). The same entities are recorded as the `ccd_system` sweeps.
+pub fn record_ccd_system(mut query: Query<(&mut Ccd, AnyOf<(&Sepax, &SepaxCompound)>), (With<Movable>, Without<NoCollision>, Without<Parent>)>)
+{
+
+    for (mut ccd, shapes) in query.iter_mut()
+    {
+
+        ccd.previous = convexes(shapes).first().map(|convex| convex.shape().position());
+
+    }
+
+}
+
+/// Compares the pairs of entities which collided this frame to those of the previous frame,
+/// sending a [`SepaxCollisionEvent`](crate::events::SepaxCollisionEvent) for each pair which
+/// started or stopped colliding. A [`SepaxContactEvent`](crate::events::SepaxContactEvent) is
+/// also sent for each collision which was resolved this frame.
+pub fn collision_event_system(pairs: Option<ResMut<CollisionPairs>>, mut events: EventWriter<SepaxCollisionEvent>, mut contacts: EventWriter<SepaxContactEvent>)
+{
+
+    let Some(mut pairs) = pairs else { return; };
+
+    contacts.send_batch(pairs.take_contacts());
+    events.send_batch(pairs.finish_frame());
+
+}
+
+//The shapes of an entity with either a Sepax or a SepaxCompound
+pub(crate) fn convexes<'a>((sepax, compound): (Option<&'a Sepax>, Option<&'a SepaxCompound>)) -> &'a [Convex]
+{
+
+    match (sepax, compound)
+    {
+
+        (Some(sepax), _) => std::slice::from_ref(&sepax.convex),
+        (None, Some(compound)) => compound.shapes(),
+        (None, None) => &[]
+
+    }
+
+}
+
+//The mutable shapes of an entity with either a Sepax or a SepaxCompound
+fn convexes_mut<'a>(sepax: &'a mut Option<Mut<Sepax>>, compound: &'a mut Option<Mut<SepaxCompound>>) -> &'a mut [Convex]
+{
+
+    match (sepax, compound)
+    {
+
+        (Some(sepax), _) => std::slice::from_mut(&mut sepax.convex),
+        (None, Some(compound)) => compound.shapes_mut(),
+        (None, None) => &mut []
+
+    }
+
+}
+
+//Returns true if any of the left shapes overlaps any of the right shapes
+fn overlaps(left: &[Convex], right: &[Convex]) -> bool
+{
+
+    left.iter().any(|left| right.iter().any(|right| sat_overlap(left.shape(), right.shape())))
+
+}
+
+//Moves every shape by the given offset
+fn translate(shapes: &mut [Convex], offset: (f32, f32))
+{
+
+    for convex in shapes.iter_mut()
+    {
+
+        let shape = convex.shape_mut();
+        let position = shape.position();
+
+        shape.set_position((position.0 + offset.0, position.1 + offset.1));
+
+    }
+
+}
+
+//Moves the shapes and their transform by the given offset
+fn shift(shapes: &mut [Convex], transform: &mut Transform, offset: (f32, f32))
+{
+
+    translate(shapes, offset);
+
+    transform.translation.x += offset.0;
+    transform.translation.y += offset.1;
+
+}
+
+type WallQuery<'w, 's> = Query<'w, 's, (Entity, AnyOf<(&'static Sepax, &'static SepaxCompound)>, Option<&'static CollisionLayers>, Has<Sensor>), (Without<Movable>, Without<NoCollision>)>;
+
+//The immovable shapes that movable shapes are resolved against, in order of their Entity
+struct Walls<'a, 'w, 's>
+{
+
+    query: &'a WallQuery<'w, 's>,
+    broadphase: Option<&'a SpatialHash>,
+    order: OnceCell<Vec<Entity>>,
+    iterations: usize,
+    suppress: bool
+
+}
+
+impl<'a, 'w, 's> Walls<'a, 'w, 's>
+{
+
+    fn new(query: &'a WallQuery<'w, 's>, broadphase: Option<&'a SpatialHash>, settings: Option<&SepaxSettings>) -> Self
+    {
+
+        let settings = settings.cloned().unwrap_or_default();
+
+        Walls { query, broadphase, order: OnceCell::new(), iterations: settings.iterations.max(1), suppress: settings.suppress_ghost_edges }
+
+    }
+
+    //The walls which may overlap the given bounds, in order of their Entity
+    fn candidates(&self, bounds: Bounds) -> Cow<'_, [Entity]>
+    {
+
+        match self.broadphase
+        {
+
+            Some(hash) if hash.worthwhile(bounds) => Cow::Owned(hash.query(bounds)),
+
+            //Without a broadphase, or for bounds covering more cells than there are walls, every
+            //wall is a candidate
+            _ => Cow::Borrowed(self.order.get_or_init(||
+            {
+
+                let mut order: Vec<Entity> = self.query.iter().map(|(entity, ..)| entity).collect();
+                order.sort();
+
+                order
+
+            }))
+
+        }
+
+    }
+
+    //Pushes the shapes out of every wall they collide with, returning the total correction
+    fn resolve(&self, moving: Entity, shapes: &mut [Convex], correct: &mut Movable, layers: Option<&CollisionLayers>, sensor: bool, mut pairs: Option<&mut CollisionPairs>) -> (f32, f32)
+    {
+
+        let mut total = (0.0, 0.0);
+
+        for _iteration in 0..self.iterations
+        {
+
+            //Every overlapping pair of parts as (depth, wall, wall part, shape), found before any are resolved
+            let mut contacts: Vec<(f32, Entity, usize, usize)> = Vec::new();
+
+            for entity in self.candidates(union_bounds(shapes)).iter().copied()
+            {
+
+                let Ok((_entity, wall, wall_layers, wall_sensor)) = self.query.get(entity) else { continue; };
+
+                if sensor || wall_sensor || !CollisionLayers::compatible(layers, wall_layers)
+                {
+
+                    continue;
+
+                }
+
+                for (j, part) in convexes(wall).iter().enumerate()
+                {
+
+                    for (i, shape) in shapes.iter().enumerate()
+                    {
+
+                        let depth = length(sat_collision(part.shape(), shape.shape()));
+
+                        if depth > f32::EPSILON
+                        {
+
+                            contacts.push((depth, entity, j, i));
+
+                        }
+
+                    }
+
+                }
+
+            }
+
+            if contacts.is_empty()
+            {
+
+                break;
+
+            }
+
+            //The sort is stable, so ties stay in order of their Entity
+            contacts.sort_by(|left, right| right.0.total_cmp(&left.0));
+
+            for (_depth, entity, j, i) in contacts
+            {
+
+                let Ok((_entity, wall, ..)) = self.query.get(entity) else { continue; };
+                let part = &convexes(wall)[j];
+
+                //An earlier correction may have already pushed the shapes out of this one
+                let correction = self.correction(entity, j, part, &shapes[i], layers);
+                let depth = length(correction);
+
+                if depth > f32::EPSILON
+                {
+
+                    translate(shapes, correction);
+
+                    let normal = (correction.0 / depth, correction.1 / depth);
+                    let record = CollisionRecord::new(entity, normal, depth, contact_manifold(part, &shapes[i], normal));
+
+                    total = (total.0 + correction.0, total.1 + correction.1);
+                    correct.collisions.push(record);
+                    if let Some(pairs) = pairs.as_deref_mut()
+                    {
+
+                        pairs.insert_contact(moving, record);
+
+                    }
+
+                }
+
+            }
+
+        }
+
+        //Sensors only report overlap with the final position and are never resolved
+        for entity in self.candidates(union_bounds(shapes)).iter().copied()
+        {
+
+            let Ok((_entity, wall, wall_layers, wall_sensor)) = self.query.get(entity) else { continue; };
+
+            if (sensor || wall_sensor) && CollisionLayers::compatible(layers, wall_layers) && overlaps(convexes(wall), shapes)
+            {
+
+                if let Some(pairs) = pairs.as_deref_mut()
+                {
+
+                    pairs.insert(moving, entity);
+
+                }
+
+            }
+
+        }
+
+        total
+
+    }
+
+    //The correction which pushes the shape out of the given part of a wall, avoiding internal edges if enabled
+    fn correction(&self, entity: Entity, index: usize, part: &Convex, shape: &Convex, layers: Option<&CollisionLayers>) -> (f32, f32)
+    {
+
+        let correction = sat_collision(part.shape(), shape.shape());
+
+        if !self.suppress || length(correction) <= f32::EPSILON
+        {
+
+            return correction;
+
+        }
+
+        //Every way of pushing the shape out along one of the separating axes, shallowest first
+        let mut pushes: Vec<(f32, f32)> = axes(part.shape(), shape.shape()).chain(axes(shape.shape(), part.shape()))
+        .flat_map(|axis| [axis, (-axis.0, -axis.1)])
+        .filter_map(|axis|
+        {
+
+            let (_min, max) = part.shape().project(axis, true);
+            let (min, _max) = shape.shape().project(axis, true);
+
+            let depth = max - min;
+            (depth > f32::EPSILON).then_some((axis.0 * depth, axis.1 * depth))
+
+        }).collect();
+
+        pushes.sort_by(|left, right| length(*left).total_cmp(&length(*right)));
+
+        //Every probe lies within the longest push of the two shapes, so one set of walls serves them all
+        let reach = pushes.iter().map(|push| length(*push)).fold(length(correction), f32::max) + GHOST_PROBE;
+        let (min, max) = union_bounds(&[part.clone(), shape.clone()]);
+        let candidates = self.candidates(((min.0 - reach, min.1 - reach), (max.0 + reach, max.1 + reach)));
+
+        if !self.internal(&candidates, entity, index, part, shape, correction, layers)
+        {
+
+            return correction;
+
+        }
+
+        //If every face is internal, such as when the shape is buried, the usual correction is kept
+        pushes.into_iter().find(|push| !self.internal(&candidates, entity, index, part, shape, *push, layers)).unwrap_or(correction)
+
+    }
+
+    //Whether pushing the shape out of the wall by the correction pushes it into one of the candidate walls
+    #[allow(clippy::too_many_arguments)]
+    fn internal(&self, candidates: &[Entity], entity: Entity, index: usize, part: &Convex, shape: &Convex, correction: (f32, f32), layers: Option<&CollisionLayers>) -> bool
+    {
+
+        let depth = length(correction);
+        let normal = (correction.0 / depth, correction.1 / depth);
+
+        let mut moved = [shape.clone()];
+        translate(&mut moved, correction);
+
+        let center = contact_manifold(part, &moved[0], normal).center();
+        let probe = (center.0 + (normal.0 * GHOST_PROBE), center.1 + (normal.1 * GHOST_PROBE));
+
+        candidates.iter().any(|&other|
+        {
+
+            let Ok((_entity, wall, wall_layers, wall_sensor)) = self.query.get(other) else { return false; };
+
+            !wall_sensor && CollisionLayers::compatible(layers, wall_layers) &&
+            convexes(wall).iter().enumerate().any(|(k, neighbour)| (other, k) != (entity, index) && contains_point(neighbour.shape(), probe))
+
+        })
+
+    }
+
+}
+
+//The normalized separating axes that the shape provides against the other shape
+fn axes<'a>(shape: &'a dyn Shape, other: &'a dyn Shape) -> impl Iterator<Item = (f32, f32)> + 'a
+{
+
+    (0..shape.num_axes()).map(move |i|
+    {
+
+        let closest = if shape.needs_closest(i) { other.get_closest(shape.point(i)) } else { (0.0, 0.0) };
+        let axis = shape.get_axis(i, closest);
+        let length = length(axis);
+
+        if length > f32::EPSILON { (axis.0 / length, axis.1 / length) } else { (0.0, 0.0) }
+
+    }).filter(|axis| *axis != (0.0, 0.0))
+
+}
+
+fn length(vector: (f32, f32)) -> f32
+{
+
+    f32::sqrt((vector.0 * vector.0) + (vector.1 * vector.1))
+
+}
+
+#[cfg(test)]
+mod plugin_tests
+{
+
+    use super::*;
+    use crate::float_equal;
+
+    fn run_collision(broadphase: Option<f32>) -> Vec<(f32, f32)>
+    {
+
+        let mut app = App::new();
+
+        match broadphase
+        {
+
+            Some(cell_size) => app.add_plugins(SepaxPlugin.with_broadphase(cell_size)),
+            None => app.add_plugins(SepaxPlugin)
+
+        };
+
+        for i in 0..20
+        {
+
+            let x = (i as f32) * 10.0 - 100.0;
+            app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((x, -10.0), 10.0, 10.0)) });
+            app.world.spawn(Sepax { convex: Convex::Circle(Circle::new((x, 40.0), 6.0)) });
+
+        }
+
+        let mut movables = Vec::new();
+        for i in 0..5
+        {
+
+            let position = ((i as f32) * 33.0 - 80.0, -1.5 + (i as f32));
+            let convex = Convex::Capsule(Capsule::new(position, (3.0, 5.0), 4.0));
+
+            movables.push(app.world.spawn((Sepax { convex }, Movable::default(), Transform::from_xyz(position.0, position.1, 0.0))).id());
+
+        }
+
+        app.update();
+
+        movables.iter().map(|entity|
+        {
+
+            let translation = app.world.get::<Transform>(*entity).unwrap().translation;
+            (translation.x, translation.y)
+
+        }).collect()
+
+    }
+
+    #[test]
+    fn test_collision_events()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let wall = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0)) }).id();
+        let player = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default())).id();
+
+        let mut reader = app.world.resource_mut::<Events<SepaxCollisionEvent>>().get_reader();
+
+        app.update();
+        let events: Vec<SepaxCollisionEvent> = reader.read(app.world.resource::<Events<SepaxCollisionEvent>>()).copied().collect();
+        assert_eq!(events, vec![SepaxCollisionEvent::Started(player, wall)]);
+
+        app.world.get_mut::<Transform>(player).unwrap().translation.y = 1.0;
+        app.update();
+        let events: Vec<SepaxCollisionEvent> = reader.read(app.world.resource::<Events<SepaxCollisionEvent>>()).copied().collect();
+        assert!(events.is_empty());
+
+        app.world.get_mut::<Transform>(player).unwrap().translation.y = 50.0;
+        app.update();
+        let events: Vec<SepaxCollisionEvent> = reader.read(app.world.resource::<Events<SepaxCollisionEvent>>()).copied().collect();
+        assert_eq!(events, vec![SepaxCollisionEvent::Stopped(player, wall)]);
+
+    }
+
+    #[test]
+    fn test_collision_records()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let floor = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0)) }).id();
+        let player = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 1.0), 2.0)) }, Movable::default(), Transform::from_xyz(0.0, 1.0, 0.0))).id();
+
+        app.update();
+
+        let movable = app.world.get::<Movable>(player).unwrap();
+        assert_eq!(movable.collisions.len(), 1);
+
+        let record = movable.collisions[0];
+        assert_eq!(record.other, floor);
+        assert!(float_equal(record.depth, 1.0));
+        assert!(float_equal(record.normal.0, 0.0) && float_equal(record.normal.1, 1.0));
+        assert!(float_equal(record.point.0, 0.0) && float_equal(record.point.1, 0.0));
+        assert_eq!(record.contacts.points().len(), 1);
+
+        let contacts: Vec<SepaxContactEvent> = app.world.resource_mut::<Events<SepaxContactEvent>>().drain().collect();
+        assert_eq!(contacts, vec![SepaxContactEvent { movable: player, record }]);
+
+    }
+
+    #[test]
+    fn test_contact_manifolds()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0)) });
+        let resting = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((2.0, -0.5), 4.0, 4.0)) }, Movable::default(), Transform::from_xyz(2.0, -0.5, 0.0))).id();
+
+        app.update();
+
+        let record = app.world.get::<Movable>(resting).unwrap().collisions[0];
+        let points = record.contacts.points();
+
+        assert_eq!(points.len(), 2);
+        assert!(float_equal(points[0].0, 2.0) && float_equal(points[0].1, 0.0));
+        assert!(float_equal(points[1].0, 6.0) && float_equal(points[1].1, 0.0));
+        assert!(float_equal(record.point.0, 4.0) && float_equal(record.point.1, 0.0));
+
+    }
+
+    #[test]
+    fn test_collision_layers()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0)) }, CollisionLayers::new(0b10, 0b01)));
+
+        let filtered = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default(), CollisionLayers::new(0b01, 0b01))).id();
+        let colliding = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default(), CollisionLayers::new(0b01, 0b10))).id();
+
+        app.update();
+
+        assert!(app.world.get::<Movable>(filtered).unwrap().collisions.is_empty());
+        assert!(app.world.get::<Movable>(colliding).unwrap().below());
+
+    }
+
+    #[test]
+    fn test_sensors()
+    {
+
+        let mut app = App::new();
+        app.insert_resource(SpatialHash::new(8.0)).add_plugins(SepaxPlugin);
+
+        let checkpoint = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0)) }, Sensor)).id();
+        let player = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default())).id();
+
+        app.update();
+
+        assert!(app.world.get::<Movable>(player).unwrap().collisions.is_empty());
+        assert!(app.world.get::<Transform>(player).unwrap().translation.y.abs() < f32::EPSILON);
+        assert!(app.world.resource::<CollisionPairs>().contains(checkpoint, player));
+
+    }
+
+    #[test]
+    fn test_movable_sensors()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        //Neither the laser nor the enemies have a Mass
+        let laser = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default(), Sensor)).id();
+        let enemy = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((1.0, 0.0), 2.0)) }, Movable::default(), Transform::from_xyz(1.0, 0.0, 0.0))).id();
+        let other = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((2.0, 0.0), 2.0)) }, Movable::default(), Transform::from_xyz(2.0, 0.0, 0.0))).id();
+        let far = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((50.0, 0.0), 2.0)) }, Movable::default(), Transform::from_xyz(50.0, 0.0, 0.0))).id();
+
+        let mut reader = app.world.resource_mut::<Events<SepaxCollisionEvent>>().get_reader();
+        app.update();
+
+        let pairs = app.world.resource::<CollisionPairs>();
+        assert!(pairs.contains(laser, enemy) && pairs.contains(laser, other));
+        assert!(!pairs.contains(enemy, other) && !pairs.contains(laser, far));
+
+        let events = reader.read(app.world.resource::<Events<SepaxCollisionEvent>>()).count();
+        assert_eq!(events, 2);
+
+        //Nothing is pushed
+        assert!(float_equal(app.world.get::<Transform>(enemy).unwrap().translation.x, 1.0));
+        assert!(app.world.get::<Movable>(enemy).unwrap().collisions.is_empty());
+
+    }
+
+    #[test]
+    fn test_mass_ratio()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let light = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default(), Mass(1.0))).id();
+        let heavy = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((3.0, 0.0), 2.0)) }, Movable::default(), Transform::from_xyz(3.0, 0.0, 0.0), Mass(3.0))).id();
+        let ghost = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((1.0, 0.0), 2.0)) }, Movable::default(), Transform::from_xyz(1.0, 0.0, 0.0))).id();
+
+        app.update();
+
+        let light_x = app.world.get::<Transform>(light).unwrap().translation.x;
+        let heavy_x = app.world.get::<Transform>(heavy).unwrap().translation.x;
+
+        assert!((light_x + 0.75).abs() < 0.0001);
+        assert!((heavy_x - 3.25).abs() < 0.0001);
+        assert!(app.world.get::<Movable>(light).unwrap().right());
+        assert!(app.world.get::<Movable>(heavy).unwrap().left());
+        assert!(app.world.get::<Movable>(ghost).unwrap().collisions.is_empty());
+
+    }
+
+    #[test]
+    fn test_pair_order()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let first = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default(), Mass(1.0))).id();
+        let second = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((3.0, 0.0), 2.0)) }, Movable::default(), Transform::from_xyz(3.0, 0.0, 0.0), Mass(1.0))).id();
+
+        //Keeps the bodies overlapping at the start of every frame
+        let overlap = |app: &mut App|
+        {
+
+            app.world.get_mut::<Transform>(first).unwrap().translation.x = 0.0;
+            app.world.get_mut::<Transform>(second).unwrap().translation.x = 3.0;
+            app.update();
+
+        };
+
+        overlap(&mut app);
+
+        let mut reader = app.world.resource_mut::<Events<SepaxCollisionEvent>>().get_reader();
+
+        //Changing the archetype of either body changes the order that they are queried in
+        app.world.entity_mut(first).insert(Name::new("first"));
+        overlap(&mut app);
+        app.world.entity_mut(second).insert(Sensor);
+        overlap(&mut app);
+        app.world.entity_mut(second).remove::<Sensor>();
+        overlap(&mut app);
+
+        let events: Vec<SepaxCollisionEvent> = reader.read(app.world.resource::<Events<SepaxCollisionEvent>>()).copied().collect();
+        assert!(events.is_empty(), "{:?}", events);
+        assert!(app.world.resource::<CollisionPairs>().colliding().eq([(first.min(second), first.max(second))]));
+
+    }
+
+    #[test]
+    fn test_static_spawn()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let quarter = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        let rotated = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((0.0, 0.0), 100.0, 2.0)) }, Transform::from_rotation(quarter))).id();
+        let scaled = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((10.0, 0.0), 10.0, 2.0)) }, Transform::from_xyz(10.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)))).id();
+
+        //The Transform is at the centre of the wall, which is rotated and scaled about it
+        let centred = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-50.0, -5.0), 20.0, 10.0)) }, Transform::from_xyz(-40.0, 0.0, 0.0).with_rotation(quarter).with_scale(Vec3::new(1.0, 0.5, 1.0)))).id();
+
+        app.update();
+
+        let shape = |app: &App, entity: Entity| app.world.get::<Sepax>(entity).unwrap().convex.clone();
+
+        assert!(contains_point(shape(&app, rotated).shape(), (-1.0, 50.0)));
+        assert!(!contains_point(shape(&app, rotated).shape(), (50.0, 1.0)));
+
+        assert!(contains_point(shape(&app, scaled).shape(), (25.0, 3.0)));
+        assert!(!contains_point(shape(&app, scaled).shape(), (31.0, 1.0)));
+
+        assert!(contains_point(shape(&app, centred).shape(), (-40.0, 9.0)) && contains_point(shape(&app, centred).shape(), (-38.0, -9.0)));
+        assert!(!contains_point(shape(&app, centred).shape(), (-45.0, 0.0)) && !contains_point(shape(&app, centred).shape(), (-40.0, 11.0)));
+        assert_eq!(app.world.get::<SepaxOffset>(centred), Some(&SepaxOffset((-10.0, -5.0))));
+
+        //Moving the Transform later moves the shape by the same amount, without jumping
+        app.world.get_mut::<Transform>(centred).unwrap().translation.x = -30.0;
+        app.update();
+
+        assert!(contains_point(shape(&app, centred).shape(), (-30.0, 9.0)) && contains_point(shape(&app, centred).shape(), (-28.0, -9.0)));
+        assert!(!contains_point(shape(&app, centred).shape(), (-40.0, 9.0)));
+
+    }
+
+    #[test]
+    fn test_rotation_sync()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let convex = Convex::Capsule(Capsule::new((0.0, 0.0), (0.0, 10.0), 1.0));
+        let transform = Transform::from_xyz(0.0, 20.0, 0.0).with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+        let hazard = app.world.spawn((Sepax { convex }, Movable::default(), transform)).id();
+
+        app.update();
+
+        assert!(app.world.get::<SepaxLocal>(hazard).is_some());
+
+        let sepax = app.world.get::<Sepax>(hazard).unwrap();
+        assert!(contains_point(sepax.shape(), (-9.0, 20.0)));
+        assert!(!contains_point(sepax.shape(), (0.0, 29.0)));
+
+        app.world.get_mut::<Transform>(hazard).unwrap().rotation = Quat::IDENTITY;
+        app.update();
+
+        let sepax = app.world.get::<Sepax>(hazard).unwrap();
+        assert!(contains_point(sepax.shape(), (0.0, 29.0)));
+
+        app.world.get_mut::<Transform>(hazard).unwrap().scale = Vec3::new(1.0, 2.0, 1.0);
+        app.update();
+
+        let sepax = app.world.get::<Sepax>(hazard).unwrap();
+        assert!(contains_point(sepax.shape(), (0.0, 39.0)));
+        assert!(!contains_point(sepax.shape(), (1.5, 20.0)));
+
+    }
+
+    #[test]
+    fn test_child_colliders()
+    {
+
+        let mut app = App::new();
+        app.add_plugins((TransformPlugin, SepaxPlugin));
+
+        app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -20.0), 20.0, 9.0)) });
+
+        let root = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 1.0)) }, Movable::default(), Ccd::default(), TransformBundle::default())).id();
+        let feet = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, -10.0), 2.0)) }, Movable::default(), Ccd::default(), TransformBundle::from_transform(Transform::from_xyz(0.0, -10.0, 0.0)))).id();
+        app.world.entity_mut(root).push_children(&[feet]);
+
+        app.update();
+
+        assert!((app.world.get::<Transform>(root).unwrap().translation.y - 1.0).abs() < 0.0001);
+
+        //The root's sweep starts from where its child pushed it, and children are not swept
+        let previous = app.world.get::<Ccd>(root).unwrap().previous.unwrap();
+        assert!(float_equal(previous.0, 0.0) && float_equal(previous.1, 1.0));
+        assert!(app.world.get::<Ccd>(feet).unwrap().previous.is_none());
+        assert!(app.world.get::<Movable>(root).unwrap().below());
+        assert!(app.world.get::<Movable>(feet).unwrap().below());
+
+        let position = app.world.get::<Sepax>(feet).unwrap().shape().position();
+        assert!((position.1 + 9.0).abs() < 0.0001);
+
+        app.world.get_mut::<Transform>(root).unwrap().translation.x = 30.0;
+        app.update();
+
+        let position = app.world.get::<Sepax>(feet).unwrap().shape().position();
+        assert!((position.0 - 30.0).abs() < 0.0001);
+        assert!(app.world.get::<Movable>(root).unwrap().collisions.is_empty());
+
+    }
+
+    #[test]
+    fn test_moving_platform()
+    {
+
+        let mut app = App::new();
+        app.insert_resource(SpatialHash::new(8.0)).add_plugins(SepaxPlugin);
+
+        let platform = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((0.0, 0.0), 20.0, 4.0)) }, Transform::default())).id();
+        let player = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((50.0, 5.0), 2.0)) }, Movable::default(), Transform::from_xyz(50.0, 5.0, 0.0))).id();
+
+        //A wall whose Transform is at the centre of its sprite is not moved when it is spawned
+        let wall = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((100.0, 0.0), 10.0, 10.0)) }, Transform::from_xyz(105.0, 5.0, 0.0))).id();
+
+        app.update();
+        assert!(app.world.get::<Movable>(player).unwrap().collisions.is_empty());
+        assert_eq!(app.world.get::<Sepax>(wall).unwrap().shape().position(), (100.0, 0.0));
+
+        app.world.get_mut::<Transform>(platform).unwrap().translation.x = 40.0;
+        app.update();
+
+        assert!(float_equal(app.world.get::<Sepax>(platform).unwrap().shape().position().0, 40.0));
+        assert!(app.world.get::<Movable>(player).unwrap().below());
+
+    }
+
+    #[test]
+    fn test_offset()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -20.0), 20.0, 10.0)) });
+
+        let transform = Transform::from_xyz(0.0, -12.0, 0.0).with_rotation(Quat::from_rotation_z(std::f32::consts::PI));
+        let player = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, SepaxOffset((0.0, -3.0)), Movable::default(), transform)).id();
+
+        app.update();
+
+        //The offset is rotated to point up, placing the circle at (0, -9) and overlapping by 1
+        assert!(app.world.get::<Movable>(player).unwrap().below());
+        assert!(float_equal(app.world.get::<Transform>(player).unwrap().translation.y, -11.0));
+        assert!(float_equal(app.world.get::<Sepax>(player).unwrap().shape().position().1, -8.0));
+
+    }
+
+    #[test]
+    fn test_compound()
+    {
+
+        for broadphase in [None, Some(SpatialHash::new(8.0))]
+        {
+
+            let mut app = App::new();
+            app.add_plugins(SepaxPlugin);
+
+            if let Some(hash) = broadphase
+            {
+
+                app.insert_resource(hash);
+
+            }
+
+            //An L-shaped wall, with its origin at the corner
+            let parts = vec![Convex::AABB(AABB::new((0.0, 0.0), 30.0, 10.0)), Convex::AABB(AABB::new((0.0, 10.0), 10.0, 20.0))];
+            let wall = app.world.spawn((SepaxCompound::new(parts), Transform::from_xyz(100.0, 0.0, 0.0))).id();
+
+            //A dumbbell resting in the corner of the L, overlapping both arms by 1
+            let parts = vec![Convex::Circle(Circle::new((0.0, 0.0), 2.0)), Convex::Circle(Circle::new((10.0, 0.0), 2.0))];
+            let player = app.world.spawn((SepaxCompound::new(parts), Movable::default(), Transform::from_xyz(111.0, 11.0, 0.0))).id();
+
+            app.update();
+
+            let movable = app.world.get::<Movable>(player).unwrap();
+            assert!(movable.below() && movable.left());
+            assert!(app.world.resource::<CollisionPairs>().contains(player, wall));
+
+            let translation = app.world.get::<Transform>(player).unwrap().translation;
+            assert!(float_equal(translation.x, 112.0));
+            assert!(float_equal(translation.y, 12.0));
+
+            //Both parts moved with the entity
+            let shapes = app.world.get::<SepaxCompound>(player).unwrap().shapes();
+            assert!(float_equal(shapes[1].shape().position().0, 122.0));
+            assert!(float_equal(shapes[1].shape().position().1, 12.0));
+
+        }
+
+    }
+
+    #[test]
+    fn test_ccd()
+    {
+
+        let mut app = App::new();
+        app.add_plugins(SepaxPlugin);
+
+        let wall = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((50.0, -10.0), 1.0, 20.0)) }).id();
+
+        let laser = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Ccd::default(), Transform::default())).id();
+        let tunneler = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 5.0), 2.0)) }, Movable::default(), Transform::from_xyz(0.0, 5.0, 0.0))).id();
+
+        app.update();
+
+        app.world.get_mut::<Transform>(laser).unwrap().translation.x = 100.0;
+        app.world.get_mut::<Transform>(tunneler).unwrap().translation.x = 100.0;
+
+        app.update();
+
+        let translation = app.world.get::<Transform>(laser).unwrap().translation;
+        assert!(translation.x < 48.0 && translation.x > 47.99);
+        assert!(app.world.get::<Movable>(laser).unwrap().right());
+        assert!(app.world.resource::<CollisionPairs>().contains(laser, wall));
+
+        assert!(float_equal(app.world.get::<Transform>(tunneler).unwrap().translation.x, 100.0));
+
+        //The position after the impact is recorded for the next frame
+        let previous = app.world.get::<Ccd>(laser).unwrap().previous.unwrap();
+        assert!(float_equal(previous.0, translation.x));
+
+    }
+
+    #[test]
+    fn test_ccd_teleport()
+    {
+
+        //A long sweep through a fine broadphase checks every wall instead of millions of cells
+        let mut app = App::new();
+        app.insert_resource(SpatialHash::new(1.0));
+        app.add_plugins(SepaxPlugin);
+
+        let floor = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-1.0e6, -20.0), 2.0e6, 10.0)) }).id();
+        app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((5.0e5, -10.0), 1.0, 20.0)) });
+
+        let laser = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Ccd::default(), Transform::default())).id();
+
+        app.update();
+        app.world.get_mut::<Transform>(laser).unwrap().translation.x = 1.0e6;
+        app.update();
+
+        let translation = app.world.get::<Transform>(laser).unwrap().translation;
+        assert!(translation.x < 5.0e5 && translation.x > 4.9999e5);
+        assert_eq!(app.world.resource::<SpatialHash>().query(((0.0, -15.0), (0.0, -15.0))), vec![floor]);
+
+    }
+
+    #[test]
+    fn test_deepest_first()
+    {
+
+        let run = |reverse: bool|
+        {
+
+            let mut app = App::new();
+            app.add_plugins(SepaxPlugin);
+
+            //A box which has sunk into the tile it stands on and slightly overlaps the next one
+            let mut tiles = vec![AABB::new((-10.0, -10.0), 10.0, 10.0), AABB::new((0.0, -10.0), 10.0, 10.0)];
+            if reverse
+            {
+
+                tiles.reverse();
+
+            }
+
+            for tile in tiles
+            {
+
+                app.world.spawn(Sepax { convex: Convex::AABB(tile) });
+
+            }
+
+            let player = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-3.8, -0.5), 4.0, 4.0)) }, Movable::default(), Transform::from_xyz(-3.8, -0.5, 0.0))).id();
+
+            app.update();
+
+            let translation = app.world.get::<Transform>(player).unwrap().translation;
+            (translation.x, translation.y, app.world.get::<Movable>(player).unwrap().collisions.len())
+
+        };
+
+        let (x, y, records) = run(false);
+        assert!(float_equal(x, -3.8) && float_equal(y, 0.0));
+        assert_eq!(records, 1);
+
+        assert_eq!(run(true), (x, y, records));
+
+    }
+
+    #[test]
+    fn test_ghost_edges()
+    {
+
+        let run = |settings: SepaxSettings, broadphase: Option<f32>|
+        {
+
+            let mut app = App::new();
+            app.insert_resource(settings).add_plugins(SepaxPlugin);
+
+            if let Some(cell_size) = broadphase
+            {
+
+                app.insert_resource(SpatialHash::new(cell_size));
+
+            }
+
+            app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 10.0, 10.0)) });
+            app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((0.0, -10.0), 10.0, 10.0)) });
+
+            //A real wall standing on the floor
+            app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((10.0, -10.0), 10.0, 20.0)) });
+
+            //A narrow box landing on the seam between two tiles, and a box pushing against the wall
+            let narrow = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((-0.3, -0.5), 0.6, 4.0)) }, Movable::default(), Transform::from_xyz(-0.3, -0.5, 0.0))).id();
+            let pushing = app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((6.2, 0.0), 4.0, 4.0)) }, Movable::default(), Transform::from_xyz(6.2, 0.0, 0.0))).id();
+
+            app.update();
+
+            let narrow = app.world.get::<Movable>(narrow).unwrap();
+            let pushing = app.world.get::<Movable>(pushing).unwrap();
+
+            (narrow.below(), narrow.left() || narrow.right(), pushing.right())
+
+        };
+
+        assert_eq!(run(SepaxSettings::default().with_ghost_edge_suppression(true), None), (true, false, true));
+        assert_eq!(run(SepaxSettings::default().with_ghost_edge_suppression(true), Some(4.0)), (true, false, true));
+        assert!(run(SepaxSettings::default(), None).1);
+
+    }
+
+    #[test]
+    fn test_without_pairs()
+    {
+
+        //Systems added without the plugin still resolve collisions without the CollisionPairs resource
+        let mut app = App::new();
+        app.add_event::<SepaxCollisionEvent>().add_event::<SepaxContactEvent>();
+        app.add_systems(PostUpdate, (dynamic_collision_system, collision_system, collision_event_system).chain());
+
+        app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((-10.0, -10.0), 20.0, 10.0)) });
+        let player = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((0.0, 0.0), 2.0)) }, Movable::default(), Transform::default())).id();
+
+        app.update();
+
+        assert!(float_equal(app.world.get::<Transform>(player).unwrap().translation.y, 2.0));
+        assert_eq!(app.world.get::<Movable>(player).unwrap().collisions.len(), 1);
+
+    }
+
+    #[test]
+    fn test_broadphase_matches_brute_force()
+    {
+
+        let brute = run_collision(None);
+        let broad = run_collision(Some(16.0));
+
+        assert_eq!(brute, broad);
+        assert!(brute.iter().any(|(_x, y)| *y > 3.0));
+
+    }
+
+}
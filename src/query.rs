@@ -0,0 +1,833 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+
+use sepax2d::prelude::*;
+
+use crate::Convex;
+use crate::broadphase::{bounds_overlap, union_bounds, Bounds, SpatialHash};
+use crate::components::{CollisionLayers, Movable, NoCollision, Sensor, Sepax, SepaxCompound};
+use crate::plugin::convexes;
+
+/// A hit found by a [`SepaxQuery`] ray cast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit
+{
+
+    /// The entity whose shape was hit.
+    pub entity: Entity,
+
+    /// The distance along the ray to the hit.
+    pub distance: f32,
+
+    /// The point where the ray first touches the shape.
+    pub point: (f32, f32),
+
+    /// The normalized surface normal at the hit, pointing away from the shape. If the ray
+    /// starts inside of the shape, this points back along the ray and the distance is zero.
+    pub normal: (f32, f32)
+
+}
+
+/// A hit found by a [`SepaxQuery`] shape cast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeHit
+{
+
+    /// The entity whose shape was hit.
+    pub entity: Entity,
+
+    /// The time of impact as a fraction of the motion, from zero to one.
+    pub toi: f32,
+
+    /// The normalized contact normal, pointing away from the shape that was hit.
+    pub normal: (f32, f32),
+
+    /// The position of the cast shape at the time of impact, backed off slightly along the
+    /// motion so that it does not touch the shape that was hit. If the cast shape started
+    /// out overlapping it, this is the starting position.
+    pub position: (f32, f32)
+
+}
+
+/// Decides which entities a [`SepaxQuery`] can find. By default, every entity which is not a
+/// [`Sensor`](crate::components::Sensor) can be found.
+///
+/// `layers` are treated as the layers of the query itself, so an entity is only found when its
+/// [`CollisionLayers`](crate::components::CollisionLayers) interact with them.
+#[derive(Clone, Debug, Default)]
+pub struct SepaxFilter
+{
+
+    pub layers: CollisionLayers,
+    pub exclude: Vec<Entity>,
+    pub sensors: bool
+
+}
+
+impl SepaxFilter
+{
+
+    /// Only find entities whose layers interact with the given layers.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self
+    {
+
+        self.layers = layers;
+        self
+
+    }
+
+    /// Never find the given entity, such as the one casting the ray.
+    pub fn with_exclude(mut self, entity: Entity) -> Self
+    {
+
+        self.exclude.push(entity);
+        self
+
+    }
+
+    /// Also find [`Sensor`](crate::components::Sensor)s.
+    pub fn with_sensors(mut self) -> Self
+    {
+
+        self.sensors = true;
+        self
+
+    }
+
+    /// Returns true if an entity with the given layers can be found.
+    pub fn allows(&self, entity: Entity, layers: Option<&CollisionLayers>, sensor: bool) -> bool
+    {
+
+        (self.sensors || !sensor) && CollisionLayers::compatible(Some(&self.layers), layers) && !self.exclude.contains(&entity)
+
+    }
+
+}
+
+type ShapeQuery<'w, 's, F> = Query<'w, 's, (Entity, AnyOf<(&'static Sepax, &'static SepaxCompound)>, Option<&'static CollisionLayers>, Has<Sensor>), F>;
+
+/// A `SystemParam` for asking questions about the [`Sepax`](crate::components::Sepax) and
+/// [`SepaxCompound`](crate::components::SepaxCompound) shapes in the world, such as line of
+/// sight or hitscan weapons.
+///
+/// Shapes are found where they were last synced by the [`SepaxPlugin`](crate::plugin::SepaxPlugin),
+/// so queries made during `Update` see the world as it was at the end of the previous frame. When
+/// the [`SpatialHash`](crate::broadphase::SpatialHash) resource is present, it is used to find
+/// nearby immovable shapes. Entities with [`NoCollision`](crate::components::NoCollision) are
+/// never found.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn hitscan_system(query: SepaxQuery, players: Query<(Entity, &Transform), With<Player>>)
+/// {
+///
+///     for (entity, transform) in players.iter()
+///     {
+///
+///         let origin = (transform.translation.x, transform.translation.y);
+///         let filter = SepaxFilter::default().with_exclude(entity);
+///
+///         if let Some(hit) = query.cast_ray(origin, (1.0, 0.0), 500.0, &filter)
+///         {
+///
+///             //Damage hit.entity
+///
+///         }
+///
+///     }
+///
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct SepaxQuery<'w, 's>
+{
+
+    statics: ShapeQuery<'w, 's, (Without<Movable>, Without<NoCollision>)>,
+    movables: ShapeQuery<'w, 's, (With<Movable>, Without<NoCollision>)>,
+    broadphase: Option<Res<'w, SpatialHash>>
+
+}
+
+impl<'w, 's> SepaxQuery<'w, 's>
+{
+
+    /// Finds the first shape hit by the ray from the origin in the given direction, up to the
+    /// given distance. The direction does not need to be normalized.
+    pub fn cast_ray(&self, origin: (f32, f32), direction: (f32, f32), max_distance: f32, filter: &SepaxFilter) -> Option<RayHit>
+    {
+
+        self.cast_ray_all(origin, direction, max_distance, filter).into_iter().next()
+
+    }
+
+    /// Finds every shape hit by the ray from the origin in the given direction, up to the given
+    /// distance, sorted from nearest to farthest. Each entity is hit at most once.
+    pub fn cast_ray_all(&self, origin: (f32, f32), direction: (f32, f32), max_distance: f32, filter: &SepaxFilter) -> Vec<RayHit>
+    {
+
+        let length = f32::sqrt((direction.0 * direction.0) + (direction.1 * direction.1));
+        if length <= f32::EPSILON || max_distance.is_nan() || max_distance < 0.0
+        {
+
+            return Vec::new();
+
+        }
+
+        let direction = (direction.0 / length, direction.1 / length);
+        let end = (origin.0 + (direction.0 * max_distance), origin.1 + (direction.1 * max_distance));
+        let bounds = ((f32::min(origin.0, end.0), f32::min(origin.1, end.1)), (f32::max(origin.0, end.0), f32::max(origin.1, end.1)));
+
+        let mut hits: Vec<RayHit> = self.candidates(bounds, filter).into_iter().filter_map(|(entity, shapes)|
+        {
+
+            shapes.iter()
+            .filter_map(|convex| ray_convex(convex, origin, direction, max_distance))
+            .min_by(|left, right| left.0.total_cmp(&right.0))
+            .map(|(distance, normal)| RayHit { entity, distance, point: (origin.0 + (direction.0 * distance), origin.1 + (direction.1 * distance)), normal })
+
+        }).collect();
+
+        hits.sort_by(|left, right| left.distance.total_cmp(&right.distance).then(left.entity.cmp(&right.entity)));
+
+        hits
+
+    }
+
+    /// Sweeps the shape from its current position along the given motion, such as a character's
+    /// velocity for the frame, and finds the first shape that it hits. See
+    /// [`cast_convex`](cast_convex) for how each type of shape is swept. Remember to exclude the
+    /// entity that the shape belongs to using the filter.
+    pub fn cast_shape(&self, convex: &Convex, motion: (f32, f32), filter: &SepaxFilter) -> Option<ShapeHit>
+    {
+
+        let start = union_bounds(std::slice::from_ref(convex));
+        let bounds = ((start.0.0 + f32::min(motion.0, 0.0), start.0.1 + f32::min(motion.1, 0.0)), (start.1.0 + f32::max(motion.0, 0.0), start.1.1 + f32::max(motion.1, 0.0)));
+
+        let (entity, toi, normal) = self.candidates(bounds, filter).into_iter().filter_map(|(entity, shapes)|
+        {
+
+            shapes.iter()
+            .filter_map(|target| cast_convex(convex, motion, target))
+            .min_by(|left, right| left.0.total_cmp(&right.0))
+            .map(|(toi, normal)| (entity, toi, normal))
+
+        }).min_by(|left, right| left.1.total_cmp(&right.1).then(left.0.cmp(&right.0)))?;
+
+        //Back off along the motion so that the shape is not left touching what it hit
+        let length = f32::sqrt((motion.0 * motion.0) + (motion.1 * motion.1));
+        let safe = if length > f32::EPSILON { f32::max(toi - (CAST_SKIN / length), 0.0) } else { 0.0 };
+
+        let position = convex.shape().position();
+        let position = (position.0 + (motion.0 * safe), position.1 + (motion.1 * safe));
+
+        Some(ShapeHit { entity, toi, normal, position })
+
+    }
+
+    /// Finds every entity whose shape contains the given point, such as for picking with the
+    /// mouse, sorted by `Entity`.
+    pub fn point_query(&self, point: (f32, f32), filter: &SepaxFilter) -> Vec<Entity>
+    {
+
+        let mut found: Vec<Entity> = self.candidates((point, point), filter).into_iter()
+        .filter(|(_entity, shapes)| shapes.iter().any(|convex| contains_point(convex.shape(), point)))
+        .map(|(entity, _shapes)| entity)
+        .collect();
+
+        found.sort_unstable();
+
+        found
+
+    }
+
+    /// Finds every entity whose shape overlaps the given shape, such as the area of an explosion,
+    /// sorted by `Entity`.
+    pub fn overlap_shape(&self, convex: &Convex, filter: &SepaxFilter) -> Vec<Entity>
+    {
+
+        let mut found: Vec<Entity> = self.candidates(union_bounds(std::slice::from_ref(convex)), filter).into_iter()
+        .filter(|(_entity, shapes)| shapes.iter().any(|other| sat_overlap(convex.shape(), other.shape())))
+        .map(|(entity, _shapes)| entity)
+        .collect();
+
+        found.sort_unstable();
+
+        found
+
+    }
+
+    //The shapes of the entities allowed by the filter whose bounds overlap the given bounds
+    fn candidates(&self, bounds: Bounds, filter: &SepaxFilter) -> Vec<(Entity, &[Convex])>
+    {
+
+        let statics: Vec<_> = match self.broadphase.as_deref()
+        {
+
+            Some(hash) if hash.worthwhile(bounds) => hash.query(bounds).into_iter().filter_map(|entity| self.statics.get(entity).ok()).collect(),
+            _ => self.statics.iter().collect()
+
+        };
+
+        statics.into_iter().chain(self.movables.iter())
+        .filter(|(entity, _shapes, layers, sensor)| filter.allows(*entity, *layers, *sensor))
+        .map(|(entity, shapes, ..)| (entity, convexes(shapes)))
+        .filter(|(_entity, shapes)| bounds_overlap(union_bounds(shapes), bounds))
+        .collect()
+
+    }
+
+}
+
+/// Casts a ray from the origin in the given normalized direction against a single shape, up to
+/// the given distance. Returns the distance along the ray to the hit and the normalized surface
+/// normal there. If the origin is inside of the shape, the distance is zero and the normal points
+/// back along the ray.
+pub fn ray_convex(convex: &Convex, origin: (f32, f32), direction: (f32, f32), max_distance: f32) -> Option<(f32, (f32, f32))>
+{
+
+    match convex
+    {
+
+        Convex::Circle(circle) => ray_circle(circle.position, circle.radius, origin, direction, max_distance),
+        Convex::Polygon(_) | Convex::AABB(_) | Convex::Parallelogram(_) => ray_polygon(&convex.vertices(), origin, direction, max_distance),
+        Convex::Capsule(capsule) =>
+        {
+
+            let (position, arm, perp) = (capsule.position(), capsule.arm(), capsule.perp());
+
+            //A capsule is a rectangle around its arm with a circle on each end
+            let body = [
+                (position.0 + arm.0 + perp.0, position.1 + arm.1 + perp.1),
+                (position.0 - arm.0 + perp.0, position.1 - arm.1 + perp.1),
+                (position.0 - arm.0 - perp.0, position.1 - arm.1 - perp.1),
+                (position.0 + arm.0 - perp.0, position.1 + arm.1 - perp.1)
+            ];
+
+            [
+                ray_circle((position.0 + arm.0, position.1 + arm.1), capsule.radius(), origin, direction, max_distance),
+                ray_circle((position.0 - arm.0, position.1 - arm.1), capsule.radius(), origin, direction, max_distance),
+                ray_polygon(&body, origin, direction, max_distance)
+            ]
+            .into_iter().flatten().min_by(|left, right| left.0.total_cmp(&right.0))
+
+        }
+
+    }
+
+}
+
+fn ray_circle(center: (f32, f32), radius: f32, origin: (f32, f32), direction: (f32, f32), max_distance: f32) -> Option<(f32, (f32, f32))>
+{
+
+    let offset = (origin.0 - center.0, origin.1 - center.1);
+    let c = (offset.0 * offset.0) + (offset.1 * offset.1) - (radius * radius);
+
+    if c <= 0.0
+    {
+
+        return Some((0.0, (-direction.0, -direction.1)));
+
+    }
+
+    let b = (offset.0 * direction.0) + (offset.1 * direction.1);
+    let discriminant = (b * b) - c;
+
+    if b > 0.0 || discriminant < 0.0
+    {
+
+        return None;
+
+    }
+
+    let distance = -b - discriminant.sqrt();
+    if distance > max_distance
+    {
+
+        return None;
+
+    }
+
+    let normal = (offset.0 + (direction.0 * distance), offset.1 + (direction.1 * distance));
+    let length = f32::sqrt((normal.0 * normal.0) + (normal.1 * normal.1));
+
+    Some((distance, if length > f32::EPSILON { (normal.0 / length, normal.1 / length) } else { (-direction.0, -direction.1) }))
+
+}
+
+//Clips the ray against each edge of a convex polygon in either winding order
+fn ray_polygon(points: &[(f32, f32)], origin: (f32, f32), direction: (f32, f32), max_distance: f32) -> Option<(f32, (f32, f32))>
+{
+
+    let count = points.len();
+    if count < 3
+    {
+
+        return None;
+
+    }
+
+    let mut area = 0.0;
+    for i in 0..count
+    {
+
+        let (a, b) = (points[i], points[(i + 1) % count]);
+        area += (a.0 * b.1) - (b.0 * a.1);
+
+    }
+
+    let winding = if area < 0.0 { -1.0 } else { 1.0 };
+
+    let mut enter = 0.0;
+    let mut exit = max_distance;
+    let mut normal = None;
+
+    for i in 0..count
+    {
+
+        let (a, b) = (points[i], points[(i + 1) % count]);
+        let edge = (b.0 - a.0, b.1 - a.1);
+        let length = f32::sqrt((edge.0 * edge.0) + (edge.1 * edge.1));
+
+        if length <= f32::EPSILON
+        {
+
+            continue;
+
+        }
+
+        //The outward normal of the edge
+        let outward = (winding * edge.1 / length, -winding * edge.0 / length);
+
+        let distance = (outward.0 * (a.0 - origin.0)) + (outward.1 * (a.1 - origin.1));
+        let speed = (outward.0 * direction.0) + (outward.1 * direction.1);
+
+        if speed.abs() <= f32::EPSILON
+        {
+
+            //Parallel to the edge, so the ray misses if it starts outside of it
+            if distance < 0.0
+            {
+
+                return None;
+
+            }
+
+            continue;
+
+        }
+
+        let time = distance / speed;
+        if speed < 0.0
+        {
+
+            if time > enter
+            {
+
+                enter = time;
+                normal = Some(outward);
+
+            }
+
+        }
+        else if time < exit
+        {
+
+            exit = time;
+
+        }
+
+        if enter > exit
+        {
+
+            return None;
+
+        }
+
+    }
+
+    Some((enter, normal.unwrap_or((-direction.0, -direction.1))))
+
+}
+
+/// Sweeps the moving shape from its current position along the given motion and finds the first
+/// time that it touches the target shape. Returns the time of impact as a fraction of the motion,
+/// from zero to one, and the normalized contact normal pointing away from the target. If the
+/// shapes already overlap, the time is zero and the normal points in the direction that the
+/// moving shape should be pushed to separate them.
+///
+/// Polygons, AABBs, and parallelograms are swept exactly. Circles and capsules have curved sides,
+/// so the time of impact is refined over several iterations, each adding the axes between the
+/// closest features of the shapes at the latest estimate.
+pub fn cast_convex(moving: &Convex, motion: (f32, f32), target: &Convex) -> Option<(f32, (f32, f32))>
+{
+
+    let start = moving.shape().position();
+
+    let mut axes = Vec::new();
+    separating_axes(moving.shape(), target.shape(), &mut axes);
+
+    let (enter, exit, normal) = sweep(moving.shape(), motion, target.shape(), &axes)?;
+
+    if exit <= 0.0 || enter > 1.0
+    {
+
+        return None;
+
+    }
+
+    if enter <= 0.0
+    {
+
+        let push = sat_collision(target.shape(), moving.shape());
+        let length = f32::sqrt((push.0 * push.0) + (push.1 * push.1));
+
+        let normal = if length > f32::EPSILON { (push.0 / length, push.1 / length) } else { normal };
+
+        return Some((0.0, normal));
+
+    }
+
+    let mut time = enter;
+    let mut normal = normal;
+    let mut placed = moving.clone();
+
+    for _ in 0..CAST_ITERATIONS
+    {
+
+        placed.shape_mut().set_position((start.0 + (motion.0 * time), start.1 + (motion.1 * time)));
+
+        let count = axes.len();
+        separating_axes(placed.shape(), target.shape(), &mut axes);
+
+        if axes.len() == count
+        {
+
+            break;
+
+        }
+
+        let (enter, exit, new_normal) = sweep(moving.shape(), motion, target.shape(), &axes)?;
+
+        if enter > exit || enter > 1.0
+        {
+
+            return None;
+
+        }
+
+        let converged = enter - time <= CAST_TOLERANCE;
+
+        time = f32::max(time, enter);
+        normal = new_normal;
+
+        if converged
+        {
+
+            break;
+
+        }
+
+    }
+
+    Some((time, normal))
+
+}
+
+const CAST_ITERATIONS: usize = 32;
+const CAST_TOLERANCE: f32 = 0.00001;
+pub(crate) const CAST_SKIN: f32 = 0.001;
+
+//The normalized axes that sepax2d would check for the two shapes in their current positions,
+//skipping any which are already present
+fn separating_axes(left: &dyn Shape, right: &dyn Shape, axes: &mut Vec<(f32, f32)>)
+{
+
+    for (shape, other) in [(left, right), (right, left)]
+    {
+
+        for i in 0..shape.num_axes()
+        {
+
+            let closest = if shape.needs_closest(i) { other.get_closest(shape.point(i)) } else { (0.0, 0.0) };
+            let axis = shape.get_axis(i, closest);
+            let length = f32::sqrt((axis.0 * axis.0) + (axis.1 * axis.1));
+
+            if length <= f32::EPSILON
+            {
+
+                continue;
+
+            }
+
+            let axis = (axis.0 / length, axis.1 / length);
+            let parallel = axes.iter().any(|other: &(f32, f32)| ((axis.0 * other.1) - (axis.1 * other.0)).abs() <= CAST_TOLERANCE);
+
+            if !parallel
+            {
+
+                axes.push(axis);
+
+            }
+
+        }
+
+    }
+
+}
+
+//The times, as fractions of the motion, that the shapes start and stop overlapping on every axis,
+//along with the normal of the last axis to start overlapping
+fn sweep(moving: &dyn Shape, motion: (f32, f32), target: &dyn Shape, axes: &[(f32, f32)]) -> Option<(f32, f32, (f32, f32))>
+{
+
+    let mut enter = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    let mut normal = (0.0, 0.0);
+
+    for axis in axes.iter()
+    {
+
+        let (moving_min, moving_max) = moving.project(*axis, true);
+        let (target_min, target_max) = target.project(*axis, true);
+        let speed = (motion.0 * axis.0) + (motion.1 * axis.1);
+
+        if speed.abs() <= f32::EPSILON
+        {
+
+            //Not moving along this axis, so they must already overlap on it
+            if moving_max <= target_min || moving_min >= target_max
+            {
+
+                return None;
+
+            }
+
+            continue;
+
+        }
+
+        let first = (target_min - moving_max) / speed;
+        let second = (target_max - moving_min) / speed;
+        let (start, end) = if first < second { (first, second) } else { (second, first) };
+
+        if start > enter
+        {
+
+            enter = start;
+            normal = if speed > 0.0 { (-axis.0, -axis.1) } else { *axis };
+
+        }
+
+        exit = f32::min(exit, end);
+
+        if enter > exit
+        {
+
+            return None;
+
+        }
+
+    }
+
+    Some((enter, exit, normal))
+
+}
+
+#[cfg(test)]
+mod query_tests
+{
+
+    use super::*;
+    use crate::float_equal;
+    use crate::plugin::SepaxPlugin;
+
+    #[test]
+    fn test_ray_shapes()
+    {
+
+        let shapes = [
+            Convex::Circle(Circle::new((10.0, 0.0), 2.0)),
+            Convex::AABB(AABB::new((8.0, -1.0), 4.0, 2.0)),
+            Convex::Parallelogram(Parallelogram::rectangle((8.0, -1.0), 4.0, 2.0)),
+            Convex::Polygon(Polygon::from_vertices((10.0, 0.0), vec![(-2.0, -1.0), (-2.0, 1.0), (2.0, 0.0)])),
+            Convex::Capsule(Capsule::new((12.0, 0.0), (2.0, 0.0), 2.0))
+        ];
+
+        for convex in shapes.iter()
+        {
+
+            let (distance, normal) = ray_convex(convex, (0.0, 0.0), (1.0, 0.0), 100.0).unwrap();
+
+            assert!(float_equal(distance, 8.0));
+            assert!(float_equal(normal.0, -1.0) && float_equal(normal.1, 0.0));
+
+            assert!(ray_convex(convex, (0.0, 0.0), (1.0, 0.0), 7.0).is_none());
+            assert!(ray_convex(convex, (0.0, 0.0), (-1.0, 0.0), 100.0).is_none());
+            assert!(ray_convex(convex, (0.0, 5.0), (1.0, 0.0), 100.0).is_none());
+
+        }
+
+        //Starting inside of a shape
+        let (distance, normal) = ray_convex(&shapes[1], (10.0, 0.0), (0.0, 1.0), 100.0).unwrap();
+        assert!(float_equal(distance, 0.0));
+        assert!(float_equal(normal.1, -1.0));
+
+    }
+
+    #[test]
+    fn test_cast_convex()
+    {
+
+        let wall = Convex::AABB(AABB::new((10.0, -10.0), 2.0, 20.0));
+
+        //Polygonal shapes are exact, even through thin walls
+        let square = Convex::AABB(AABB::new((0.0, 0.0), 2.0, 2.0));
+        let (toi, normal) = cast_convex(&square, (100.0, 0.0), &wall).unwrap();
+        assert!(float_equal(toi, 0.08));
+        assert!(float_equal(normal.0, -1.0) && float_equal(normal.1, 0.0));
+
+        assert!(cast_convex(&square, (7.0, 0.0), &wall).is_none());
+        assert!(cast_convex(&square, (-100.0, 0.0), &wall).is_none());
+
+        //A circle sweeping diagonally towards the corner of the wall
+        let circle = Convex::Circle(Circle::new((0.0, 13.0), 1.0));
+        let (toi, normal) = cast_convex(&circle, (20.0, -6.0), &wall).unwrap();
+        let center = (20.0 * toi, 13.0 - (6.0 * toi));
+        let distance = f32::sqrt(((center.0 - 10.0) * (center.0 - 10.0)) + ((center.1 - 10.0) * (center.1 - 10.0)));
+
+        assert!((distance - 1.0).abs() < 0.001);
+        assert!(normal.0 < 0.0 && normal.1 > 0.0);
+
+        //Capsules are refined in the same way
+        let capsule = Convex::Capsule(Capsule::new((0.0, 0.0), (0.0, 3.0), 1.0));
+        let (toi, _normal) = cast_convex(&capsule, (20.0, 0.0), &wall).unwrap();
+        assert!(float_equal(toi, 0.45));
+
+        //Already overlapping
+        let inside = Convex::Circle(Circle::new((11.8, 0.0), 1.0));
+        let (toi, normal) = cast_convex(&inside, (-1.0, 0.0), &wall).unwrap();
+        assert!(float_equal(toi, 0.0));
+        assert!(float_equal(normal.0, 1.0));
+
+    }
+
+    #[test]
+    fn test_cast_shape()
+    {
+
+        let mut app = App::new();
+        app.insert_resource(SpatialHash::new(4.0)).add_plugins(SepaxPlugin);
+
+        let wall = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((10.0, -10.0), 2.0, 20.0)) }).id();
+        app.world.spawn((Sepax { convex: Convex::AABB(AABB::new((5.0, -10.0), 1.0, 20.0)) }, NoCollision));
+
+        app.update();
+
+        let mut state: bevy::ecs::system::SystemState<SepaxQuery> = bevy::ecs::system::SystemState::new(&mut app.world);
+        let query = state.get(&app.world);
+
+        let circle = Convex::Circle(Circle::new((0.0, 0.0), 1.0));
+        let hit = query.cast_shape(&circle, (20.0, 0.0), &SepaxFilter::default()).unwrap();
+
+        assert_eq!(hit.entity, wall);
+        assert!(float_equal(hit.toi, 0.45));
+        assert!(hit.position.0 < 9.0 && hit.position.0 > 8.99);
+
+        assert!(query.cast_shape(&circle, (0.0, 20.0), &SepaxFilter::default()).is_none());
+        assert!(query.cast_shape(&circle, (20.0, 0.0), &SepaxFilter::default().with_exclude(wall)).is_none());
+
+    }
+
+    #[test]
+    fn test_overlap_queries()
+    {
+
+        for broadphase in [None, Some(SpatialHash::new(4.0))]
+        {
+
+            let mut app = App::new();
+            app.add_plugins(SepaxPlugin);
+
+            if let Some(hash) = broadphase
+            {
+
+                app.insert_resource(hash);
+
+            }
+
+            let square = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((0.0, 0.0), 10.0, 10.0)) }).id();
+            let circle = app.world.spawn(Sepax { convex: Convex::Circle(Circle::new((12.0, 5.0), 3.0)) }).id();
+            let compound = app.world.spawn((SepaxCompound::new(vec![Convex::AABB(AABB::new((0.0, 0.0), 2.0, 2.0)), Convex::AABB(AABB::new((10.0, 0.0), 2.0, 2.0))]), Transform::from_xyz(20.0, 20.0, 0.0))).id();
+            let moving = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((40.0, 40.0), 1.0)) }, Movable::default(), Transform::from_xyz(40.0, 40.0, 0.0))).id();
+
+            app.update();
+
+            let mut state: bevy::ecs::system::SystemState<SepaxQuery> = bevy::ecs::system::SystemState::new(&mut app.world);
+            let query = state.get(&app.world);
+            let filter = SepaxFilter::default();
+
+            assert_eq!(query.point_query((9.5, 5.0), &filter), vec![square, circle]);
+            assert_eq!(query.point_query((31.0, 21.0), &filter), vec![compound]);
+            assert!(query.point_query((26.0, 21.0), &filter).is_empty());
+            assert_eq!(query.point_query((40.0, 40.5), &filter), vec![moving]);
+
+            let explosion = Convex::Circle(Circle::new((25.0, 25.0), 5.0));
+            assert_eq!(query.overlap_shape(&explosion, &filter), vec![compound]);
+
+            let explosion = Convex::AABB(AABB::new((5.0, 5.0), 40.0, 40.0));
+            assert_eq!(query.overlap_shape(&explosion, &filter), vec![square, circle, compound, moving]);
+            assert_eq!(query.overlap_shape(&explosion, &filter.clone().with_exclude(square)), vec![circle, compound, moving]);
+
+        }
+
+    }
+
+    #[test]
+    fn test_cast_ray()
+    {
+
+        for broadphase in [None, Some(SpatialHash::new(4.0))]
+        {
+
+            let mut app = App::new();
+            app.add_plugins(SepaxPlugin);
+
+            if let Some(hash) = broadphase
+            {
+
+                app.insert_resource(hash);
+
+            }
+
+            let near = app.world.spawn(Sepax { convex: Convex::AABB(AABB::new((10.0, -5.0), 2.0, 10.0)) }).id();
+            let far = app.world.spawn(Sepax { convex: Convex::Circle(Circle::new((30.0, 0.0), 3.0)) }).id();
+            let sensor = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((5.0, 0.0), 1.0)) }, Sensor)).id();
+            let moving = app.world.spawn((Sepax { convex: Convex::Circle(Circle::new((20.0, 1.0), 1.0)) }, Movable::default(), Transform::from_xyz(20.0, 1.0, 0.0))).id();
+
+            app.update();
+
+            let mut state: bevy::ecs::system::SystemState<SepaxQuery> = bevy::ecs::system::SystemState::new(&mut app.world);
+            let query = state.get(&app.world);
+
+            let hit = query.cast_ray((0.0, 0.0), (2.0, 0.0), 100.0, &SepaxFilter::default()).unwrap();
+            assert_eq!(hit.entity, near);
+            assert!(float_equal(hit.distance, 10.0));
+            assert!(float_equal(hit.point.0, 10.0));
+
+            let hits = query.cast_ray_all((0.0, 0.0), (1.0, 0.0), 100.0, &SepaxFilter::default().with_sensors());
+            assert_eq!(hits.iter().map(|hit| hit.entity).collect::<Vec<_>>(), vec![sensor, near, moving, far]);
+
+            let filter = SepaxFilter::default().with_exclude(near).with_exclude(moving);
+            assert_eq!(query.cast_ray((0.0, 0.0), (1.0, 0.0), 100.0, &filter).unwrap().entity, far);
+            assert!(query.cast_ray((0.0, 0.0), (1.0, 0.0), 26.0, &filter).is_none());
+
+        }
+
+    }
+
+}
@@ -0,0 +1,712 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use sepax2d::prelude::*;
+
+use std::path::Path;
+
+use crate::Convex;
+use crate::components::{read_fields, Sensor, Sepax, SepaxCompound};
+use crate::decompose::{decompose, DecomposeError};
+
+//Tiled stores whether a tile is flipped in the highest bits of its global id
+const FLIP_FLAGS: u32 = 0xF000_0000;
+
+/// The reasons that a Tiled map or tileset cannot be loaded.
+#[derive(Debug)]
+pub enum TiledError
+{
+
+    /// The file could not be read.
+    Io(std::io::Error),
+
+    /// The file is not valid XML.
+    Xml(roxmltree::Error),
+
+    /// An attribute is missing or could not be parsed, given as the element and attribute names.
+    Invalid(String, String),
+
+    /// The map uses a feature that is not supported, such as a tile layer which is not stored as CSV.
+    Unsupported(String),
+
+    /// A polygon could not be decomposed into convex pieces.
+    Decompose(DecomposeError)
+
+}
+
+impl std::fmt::Display for TiledError
+{
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+
+        match self
+        {
+
+            TiledError::Io(error) => write!(f, "could not read file: {}", error),
+            TiledError::Xml(error) => write!(f, "invalid xml: {}", error),
+            TiledError::Invalid(element, attribute) => write!(f, "missing or invalid attribute {} on {}", attribute, element),
+            TiledError::Unsupported(feature) => write!(f, "unsupported feature: {}", feature),
+            TiledError::Decompose(error) => write!(f, "invalid polygon: {}", error)
+
+        }
+
+    }
+
+}
+
+impl std::error::Error for TiledError {}
+
+impl From<std::io::Error> for TiledError
+{
+
+    fn from(error: std::io::Error) -> Self
+    {
+
+        TiledError::Io(error)
+
+    }
+
+}
+
+impl From<roxmltree::Error> for TiledError
+{
+
+    fn from(error: roxmltree::Error) -> Self
+    {
+
+        TiledError::Xml(error)
+
+    }
+
+}
+
+impl From<DecomposeError> for TiledError
+{
+
+    fn from(error: DecomposeError) -> Self
+    {
+
+        TiledError::Decompose(error)
+
+    }
+
+}
+
+/// A shape from a Tiled map, converted to Bevy's coordinates where y points up.
+///
+/// The `parts` are given relative to `position`, which is the object's origin in the world: the
+/// top left corner of rectangles and ellipses, or the first point of polygons. Rectangles become
+/// AABBs, or polygons if they are rotated, ellipses become circles at their center with the average
+/// of their radii, as in the LDtk importer, and polygons become one or more convex polygons, as
+/// concave polygons are decomposed with [`decompose`](crate::decompose::decompose).
+#[derive(Clone, Debug)]
+pub struct TiledObject
+{
+
+    pub id: u32,
+    pub name: String,
+
+    /// The object's class, which was called its type before Tiled 1.9.
+    pub class: String,
+
+    /// The name of the layer that the object was found in.
+    pub layer: String,
+
+    /// The object's custom properties, as written in the file.
+    pub properties: HashMap<String, String>,
+
+    pub position: (f32, f32),
+    pub parts: Vec<Convex>
+
+}
+
+impl TiledObject
+{
+
+    /// The parts of the object, placed in the world.
+    pub fn convexes(&self) -> Vec<Convex>
+    {
+
+        self.parts.iter().cloned().map(|mut part|
+        {
+
+            let shape = part.shape_mut();
+            let local = shape.position();
+
+            shape.set_position((self.position.0 + local.0, self.position.1 + local.1));
+            part
+
+        }).collect()
+
+    }
+
+    /// Spawns the object as an immovable entity with its `Name`, and either a `Sepax` or, if it has
+    /// several parts, a `SepaxCompound`. A `Sensor` is added if the object has a boolean `sensor`
+    /// property which is true, and `CollisionLayers` are added if it has integer `memberships` or
+    /// `filters` properties, with any missing one treated as every layer.
+    pub fn spawn(&self, commands: &mut Commands) -> Entity
+    {
+
+        let mut spawned = match self.convexes().as_slice()
+        {
+
+            [convex] =>
+            {
+
+                let position = convex.shape().position();
+                commands.spawn((Sepax { convex: convex.clone() }, Transform::from_xyz(position.0, position.1, 0.0)))
+
+            },
+            _ => commands.spawn((SepaxCompound::new(self.parts.clone()), Transform::from_xyz(self.position.0, self.position.1, 0.0)))
+
+        };
+
+        spawned.insert(Name::new(self.name.clone()));
+
+        let (sensor, layers) = read_fields(|name| self.properties.get(name).map(String::as_str), "sensor", "memberships", "filters");
+
+        if sensor
+        {
+
+            spawned.insert(Sensor);
+
+        }
+
+        if let Some(layers) = layers
+        {
+
+            spawned.insert(layers);
+
+        }
+
+        spawned.id()
+
+    }
+
+}
+
+/// The collision shapes of a Tiled map. Objects are read from every object layer, including
+/// those inside of group layers, and from the collision shapes that the tilesets give each tile
+/// in every tile layer. Tilesets may be embedded in the map or stored in separate `.tsx` files.
+///
+/// Positions are converted from Tiled's coordinates, where y points down from the top left corner
+/// of the map, to Bevy's, with y pointing up from the bottom left corner of the map.
+///
+/// Tile objects, points, polylines and text are ignored, as are the flipping of tiles and the
+/// rotation of ellipses. Tile layers must be stored as CSV, which is Tiled's default.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_sepax2d::tiled::TiledMap;
+///
+/// fn level_system(mut commands: Commands)
+/// {
+///
+///     let map = TiledMap::load("assets/level.tmx").unwrap();
+///     map.spawn(&mut commands);
+///
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TiledMap
+{
+
+    /// The size of the map in tiles.
+    pub width: u32,
+    pub height: u32,
+
+    /// The size of each tile.
+    pub tile_width: f32,
+    pub tile_height: f32,
+
+    pub objects: Vec<TiledObject>
+
+}
+
+impl TiledMap
+{
+
+    /// Loads a `.tmx` file, along with any `.tsx` tilesets that it uses.
+    pub fn load(path: impl AsRef<Path>) -> Result<TiledMap, TiledError>
+    {
+
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+
+        TiledMap::parse(&text, path.parent().unwrap_or(Path::new("")))
+
+    }
+
+    /// Parses the contents of a `.tmx` file. The paths of `.tsx` tilesets are relative to the
+    /// given directory, which is usually the one containing the map.
+    pub fn parse(text: &str, directory: &Path) -> Result<TiledMap, TiledError>
+    {
+
+        let document = roxmltree::Document::parse(text)?;
+        let root = document.root_element();
+
+        let width = attribute(root, "width")?;
+        let height = attribute(root, "height")?;
+        let tile_width = attribute(root, "tilewidth")?;
+        let tile_height = attribute(root, "tileheight")?;
+
+        let mut map = TiledMap { width, height, tile_width, tile_height, objects: Vec::new() };
+
+        //Each tileset, sorted by the first global id of its tiles
+        let mut tilesets: Vec<(u32, TiledTileset)> = Vec::new();
+        for node in root.children().filter(|node| node.has_tag_name("tileset"))
+        {
+
+            let first: u32 = attribute(node, "firstgid")?;
+
+            let tileset = match node.attribute("source")
+            {
+
+                Some(source) => TiledTileset::load(directory.join(source))?,
+                None => TiledTileset::from_node(node)?
+
+            };
+
+            tilesets.push((first, tileset));
+
+        }
+
+        tilesets.sort_by_key(|(first, _tileset)| *first);
+
+        map.read_layers(root, (0.0, 0.0), &tilesets)?;
+
+        Ok(map)
+
+    }
+
+    /// Spawns every object with [`TiledObject::spawn`], returning the entities.
+    pub fn spawn(&self, commands: &mut Commands) -> Vec<Entity>
+    {
+
+        self.objects.iter().map(|object| object.spawn(commands)).collect()
+
+    }
+
+    //The height of the map, for flipping the y-axis
+    fn pixel_height(&self) -> f32
+    {
+
+        (self.height as f32) * self.tile_height
+
+    }
+
+    //Reads the layers inside of the map or a group layer, which are moved by the given offset
+    fn read_layers(&mut self, parent: roxmltree::Node, offset: (f32, f32), tilesets: &[(u32, TiledTileset)]) -> Result<(), TiledError>
+    {
+
+        for node in parent.children().filter(|node| node.is_element())
+        {
+
+            let offset = (offset.0 + optional(node, "offsetx", 0.0)?, offset.1 + optional(node, "offsety", 0.0)?);
+            let layer = node.attribute("name").unwrap_or_default();
+
+            match node.tag_name().name()
+            {
+
+                "group" => self.read_layers(node, offset, tilesets)?,
+                "objectgroup" =>
+                {
+
+                    let height = self.pixel_height();
+                    self.objects.extend(read_objects(node, layer, |(x, y)| (x + offset.0, height - (y + offset.1)))?);
+
+                },
+                "layer" => self.read_tiles(node, layer, offset, tilesets)?,
+                _ => {}
+
+            }
+
+        }
+
+        Ok(())
+
+    }
+
+    fn read_tiles(&mut self, node: roxmltree::Node, layer: &str, offset: (f32, f32), tilesets: &[(u32, TiledTileset)]) -> Result<(), TiledError>
+    {
+
+        let Some(data) = node.children().find(|child| child.has_tag_name("data")) else { return Ok(()); };
+
+        if data.children().any(|child| child.has_tag_name("chunk"))
+        {
+
+            return Err(TiledError::Unsupported(format!("tile layer {} is stored in chunks, as in infinite maps", layer)));
+
+        }
+
+        if data.attribute("encoding") != Some("csv")
+        {
+
+            return Err(TiledError::Unsupported(format!("tile layer {} is not stored as csv", layer)));
+
+        }
+
+        let width: u32 = attribute(node, "width")?;
+        let height = self.pixel_height();
+
+        if width == 0
+        {
+
+            return Err(TiledError::Invalid("layer".to_string(), "width".to_string()));
+
+        }
+
+        let gids = data.text().unwrap_or_default().split(',').map(str::trim).filter(|gid| !gid.is_empty());
+        for (index, gid) in gids.enumerate()
+        {
+
+            let gid = gid.parse::<u32>().map_err(|_error| TiledError::Invalid("data".to_string(), "csv".to_string()))? & !FLIP_FLAGS;
+            let Some((first, tileset)) = tilesets.iter().rev().find(|(first, _tileset)| *first <= gid) else { continue; };
+            let Some(shapes) = tileset.tiles.get(&(gid - first)) else { continue; };
+
+            let (column, row) = ((index as u32) % width, (index as u32) / width);
+
+            //Tiles which are taller than the map's tiles extend upwards from the bottom of their cell
+            let corner = (offset.0 + ((column as f32) * self.tile_width), offset.1 + (((row + 1) as f32) * self.tile_height) - tileset.tile_height);
+
+            for shape in shapes.iter()
+            {
+
+                let mut object = shape.clone();
+
+                object.layer = layer.to_string();
+                object.position = (corner.0 + shape.position.0, height - corner.1 + shape.position.1);
+
+                self.objects.push(object);
+
+            }
+
+        }
+
+        Ok(())
+
+    }
+
+}
+
+/// The collision shapes given to the tiles of a Tiled tileset, stored in a `.tsx` file or embedded
+/// in a map.
+#[derive(Clone, Debug)]
+pub struct TiledTileset
+{
+
+    pub tile_width: f32,
+    pub tile_height: f32,
+
+    /// The collision shapes of each tile with any, by the tile's id within the tileset. The
+    /// position of each object is relative to the top left corner of the tile, with y pointing up.
+    pub tiles: HashMap<u32, Vec<TiledObject>>
+
+}
+
+impl TiledTileset
+{
+
+    /// Loads a `.tsx` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<TiledTileset, TiledError>
+    {
+
+        TiledTileset::parse(&std::fs::read_to_string(path)?)
+
+    }
+
+    /// Parses the contents of a `.tsx` file.
+    pub fn parse(text: &str) -> Result<TiledTileset, TiledError>
+    {
+
+        let document = roxmltree::Document::parse(text)?;
+        TiledTileset::from_node(document.root_element())
+
+    }
+
+    fn from_node(node: roxmltree::Node) -> Result<TiledTileset, TiledError>
+    {
+
+        let mut tileset = TiledTileset { tile_width: attribute(node, "tilewidth")?, tile_height: attribute(node, "tileheight")?, tiles: HashMap::new() };
+
+        for tile in node.children().filter(|child| child.has_tag_name("tile"))
+        {
+
+            let id: u32 = attribute(tile, "id")?;
+
+            for group in tile.children().filter(|child| child.has_tag_name("objectgroup"))
+            {
+
+                let objects = read_objects(group, "", |(x, y)| (x, -y))?;
+                tileset.tiles.entry(id).or_default().extend(objects);
+
+            }
+
+        }
+
+        Ok(tileset)
+
+    }
+
+}
+
+//Reads the objects of an object layer, converting Tiled's coordinates with the given function
+fn read_objects(layer: roxmltree::Node, name: &str, convert: impl Fn((f32, f32)) -> (f32, f32)) -> Result<Vec<TiledObject>, TiledError>
+{
+
+    let mut objects = Vec::new();
+
+    for node in layer.children().filter(|child| child.has_tag_name("object"))
+    {
+
+        //Tile objects are drawn rather than collided with
+        if node.attribute("gid").is_some()
+        {
+
+            continue;
+
+        }
+
+        let (width, height) = (optional(node, "width", 0.0)?, optional(node, "height", 0.0)?);
+
+        //Tiled rotates clockwise around the origin, which is counterclockwise once y points up
+        let angle = -optional::<f32>(node, "rotation", 0.0)?.to_radians();
+        let (sin, cos) = angle.sin_cos();
+        let rotated = angle.abs() > f32::EPSILON;
+
+        let child = |tag: &str| node.children().find(|child| child.has_tag_name(tag));
+
+        let parts = if let Some(polygon) = child("polygon")
+        {
+
+            let points = points(polygon)?;
+            let points: Vec<(f32, f32)> = points.into_iter().map(|(x, y)| sepax2d::rotate!(sin, cos, (x, -y))).collect();
+
+            decompose(&points)?
+
+        }
+        else if child("ellipse").is_some()
+        {
+
+            let center = sepax2d::rotate!(sin, cos, (width / 2.0, -height / 2.0));
+            vec![Convex::Circle(Circle::new(center, (width + height) / 4.0))]
+
+        }
+        else if child("point").is_some() || child("polyline").is_some() || child("text").is_some() || width <= 0.0 || height <= 0.0
+        {
+
+            continue;
+
+        }
+        else if rotated
+        {
+
+            let corners = [(0.0, 0.0), (0.0, -height), (width, -height), (width, 0.0)];
+            vec![Convex::Polygon(Polygon::from_vertices((0.0, 0.0), corners.iter().map(|corner| sepax2d::rotate!(sin, cos, corner)).collect()))]
+
+        }
+        else
+        {
+
+            vec![Convex::AABB(AABB::new((0.0, -height), width, height))]
+
+        };
+
+        let properties = node.children().filter(|child| child.has_tag_name("properties"))
+        .flat_map(|properties| properties.children().filter(|child| child.has_tag_name("property")))
+        .filter_map(|property| Some((property.attribute("name")?.to_string(), property.attribute("value").or(property.text()).unwrap_or_default().to_string())))
+        .collect();
+
+        objects.push(TiledObject
+        {
+
+            id: optional(node, "id", 0)?,
+            name: node.attribute("name").unwrap_or_default().to_string(),
+            class: node.attribute("class").or(node.attribute("type")).unwrap_or_default().to_string(),
+            layer: name.to_string(),
+            properties,
+            position: convert((attribute(node, "x")?, attribute(node, "y")?)),
+            parts
+
+        });
+
+    }
+
+    Ok(objects)
+
+}
+
+//Parses a list of points such as "0,0 16,0 16,16"
+fn points(node: roxmltree::Node) -> Result<Vec<(f32, f32)>, TiledError>
+{
+
+    let invalid = || TiledError::Invalid(node.tag_name().name().to_string(), "points".to_string());
+
+    node.attribute("points").ok_or_else(invalid)?.split_whitespace().map(|point|
+    {
+
+        let (x, y) = point.split_once(',').ok_or_else(invalid)?;
+        Ok((x.parse().map_err(|_error| invalid())?, y.parse().map_err(|_error| invalid())?))
+
+    }).collect()
+
+}
+
+fn attribute<T: std::str::FromStr>(node: roxmltree::Node, name: &str) -> Result<T, TiledError>
+{
+
+    node.attribute(name).and_then(|value| value.parse().ok()).ok_or_else(|| TiledError::Invalid(node.tag_name().name().to_string(), name.to_string()))
+
+}
+
+fn optional<T: std::str::FromStr>(node: roxmltree::Node, name: &str, default: T) -> Result<T, TiledError>
+{
+
+    match node.attribute(name)
+    {
+
+        Some(_value) => attribute(node, name),
+        None => Ok(default)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tiled_tests
+{
+
+    use super::*;
+    use crate::components::CollisionLayers;
+    use crate::float_equal;
+
+    const MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="10" height="8" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" name="tiles" tilewidth="16" tileheight="16" tilecount="2" columns="2">
+  <tile id="1">
+   <objectgroup>
+    <object id="1" x="0" y="8" width="16" height="8"/>
+   </objectgroup>
+  </tile>
+ </tileset>
+ <layer id="1" name="ground" width="10" height="8">
+  <data encoding="csv">
+0,0,0,0,0,0,0,0,0,0,
+0,0,0,0,0,0,0,0,0,0,
+0,0,0,0,0,0,0,0,0,0,
+0,0,0,0,0,0,0,0,0,0,
+0,0,0,0,0,0,0,0,0,0,
+0,0,0,0,0,0,0,0,0,0,
+0,0,0,0,0,0,0,0,0,0,
+1,2,1,2,2147483650,0,0,0,0,0
+</data>
+ </layer>
+ <group id="2" name="walls" offsetx="4">
+  <objectgroup id="3" name="solid">
+   <object id="1" name="floor" type="wall" x="0" y="100" width="40" height="20"/>
+   <object id="2" x="10" y="10" width="8" height="12">
+    <ellipse/>
+   </object>
+   <object id="3" name="ledge" x="100" y="20">
+    <polygon points="0,0 30,0 30,10 10,10 10,30 0,30"/>
+   </object>
+   <object id="4" name="checkpoint" x="50" y="50" width="10" height="10">
+    <properties>
+     <property name="sensor" type="bool" value="true"/>
+     <property name="memberships" type="int" value="2"/>
+    </properties>
+   </object>
+   <object id="5" x="0" y="0" width="10" height="20" rotation="90"/>
+   <object id="6" x="5" y="5">
+    <point/>
+   </object>
+  </objectgroup>
+ </group>
+</map>"#;
+
+    #[test]
+    fn test_objects()
+    {
+
+        let map = TiledMap::parse(MAP, Path::new("")).unwrap();
+        let height = 8.0 * 16.0;
+
+        let find = |id: u32| map.objects.iter().find(|object| object.layer == "solid" && object.id == id).unwrap();
+
+        //Rectangles are converted to AABBs whose corner is at the bottom left
+        let floor = find(1);
+        assert_eq!((floor.name.as_str(), floor.class.as_str()), ("floor", "wall"));
+        assert!(matches!(floor.convexes()[0], Convex::AABB(aabb) if aabb.position == (4.0, height - 120.0) && aabb.width == 40.0));
+
+        //Ellipses become circles at their center with the average of their radii
+        let Convex::Circle(circle) = find(2).convexes()[0] else { panic!("expected a circle") };
+        assert!(float_equal(circle.position.0, 18.0) && float_equal(circle.position.1, height - 16.0));
+        assert!(float_equal(circle.radius, 5.0));
+
+        //The concave polygon is decomposed, keeping its area
+        let ledge = find(3);
+        assert_eq!(ledge.parts.len(), 2);
+        assert!(ledge.convexes().iter().any(|part| contains_point(part.shape(), (105.0, height - 45.0))));
+        assert!(ledge.convexes().iter().all(|part| !contains_point(part.shape(), (120.0, height - 45.0))));
+
+        //Rotating clockwise by 90 degrees in Tiled swings the rectangle to the left of its origin
+        let rotated = find(5).convexes();
+        assert!(contains_point(rotated[0].shape(), (-15.0 + 4.0, height - 5.0)));
+
+        assert!(find(4).properties.get("sensor").is_some_and(|sensor| sensor == "true"));
+        assert!(map.objects.iter().all(|object| object.id != 6 || object.layer != "solid"));
+
+    }
+
+    #[test]
+    fn test_tiles()
+    {
+
+        let map = TiledMap::parse(MAP, Path::new("")).unwrap();
+        let tiles: Vec<&TiledObject> = map.objects.iter().filter(|object| object.layer == "ground").collect();
+
+        //Only the second tile has a collision shape, including the flipped one
+        assert_eq!(tiles.len(), 3);
+
+        let Convex::AABB(aabb) = tiles[0].convexes()[0] else { panic!("expected an AABB") };
+        assert_eq!((aabb.position, aabb.width, aabb.height), ((16.0, 0.0), 16.0, 8.0));
+
+        let tileset = TiledTileset::parse(r#"<tileset name="tiles" tilewidth="8" tileheight="8"><tile id="3"><objectgroup><object id="1" x="0" y="0" width="8" height="8"/></objectgroup></tile></tileset>"#).unwrap();
+        assert_eq!(tileset.tiles[&3].len(), 1);
+
+        assert!(matches!(TiledMap::parse("<map/>", Path::new("")), Err(TiledError::Invalid(..))));
+
+        let empty = MAP.replace(r#"name="ground" width="10""#, r#"name="ground" width="0""#);
+        assert!(matches!(TiledMap::parse(&empty, Path::new("")), Err(TiledError::Invalid(..))));
+
+        let infinite = r#"<map width="16" height="16" tilewidth="16" tileheight="16" infinite="1"><layer id="1" name="ground" width="16" height="16"><data encoding="csv"><chunk x="0" y="0" width="16" height="16">1,2</chunk></data></layer></map>"#;
+        assert!(matches!(TiledMap::parse(infinite, Path::new("")), Err(TiledError::Unsupported(..))));
+
+    }
+
+    #[test]
+    fn test_spawn()
+    {
+
+        let map = TiledMap::parse(MAP, Path::new("")).unwrap();
+
+        let mut app = App::new();
+
+        let mut queue = bevy::ecs::system::CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &app.world);
+        let entities = map.spawn(&mut commands);
+        queue.apply(&mut app.world);
+
+        assert_eq!(entities.len(), map.objects.len());
+
+        let ledge = map.objects.iter().position(|object| object.name == "ledge").unwrap();
+        assert!(app.world.get::<SepaxCompound>(entities[ledge]).is_some());
+
+        let checkpoint = map.objects.iter().position(|object| object.name == "checkpoint").unwrap();
+        assert!(app.world.get::<Sensor>(entities[checkpoint]).is_some());
+        assert_eq!(app.world.get::<CollisionLayers>(entities[checkpoint]), Some(&CollisionLayers::new(2, u32::MAX)));
+
+    }
+
+}
@@ -0,0 +1,375 @@
+use bevy::prelude::*;
+
+use sepax2d::prelude::*;
+
+use std::collections::BTreeSet;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::Convex;
+use crate::components::{CollisionLayers, Sensor, Sepax};
+use crate::merge::merge_grid;
+
+/// The collision shape of a single tile in a [`SepaxTilemap`]. Slopes are right triangles which
+/// fill half of the tile, named after the corner of the tile that their right angle is in, so
+/// `SlopeBottomLeft` is a floor which rises towards the left.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TileCollider
+{
+
+    #[default]
+    Empty,
+    Solid,
+    SlopeBottomLeft,
+    SlopeBottomRight,
+    SlopeTopLeft,
+    SlopeTopRight
+
+}
+
+impl From<bool> for TileCollider
+{
+
+    fn from(solid: bool) -> Self
+    {
+
+        if solid { TileCollider::Solid } else { TileCollider::Empty }
+
+    }
+
+}
+
+/// A component describing the collision of a grid of tiles, from which the
+/// [`tilemap_system`](tilemap_system) spawns immovable colliders. Solid tiles are merged into
+/// as few AABBs as possible using [`merge_grid`](crate::merge::merge_grid), and each slope
+/// becomes a triangle.
+///
+/// The tiles are stored row by row, starting from the bottom row, and the bottom left corner of
+/// the grid is at `origin` in world space. The colliders are rebuilt whenever the component is
+/// changed, such as when a tile is set at runtime, so split very large levels into several
+/// tilemaps to keep each rebuild cheap.
+///
+/// The colliders are given the tilemap entity's [`CollisionLayers`](crate::components::CollisionLayers)
+/// and [`Sensor`](crate::components::Sensor) components, if it has them.
+///
+/// ```rust
+/// use bevy_sepax2d::prelude::*;
+///
+/// //A floor with a slope rising to the right at its left end
+/// let tilemap = SepaxTilemap::from_fn(8, 2, (16.0, 16.0), (0.0, 0.0), |x, y|
+/// {
+///
+///     match (x, y)
+///     {
+///
+///         (_, 0) => TileCollider::Solid,
+///         (0, 1) => TileCollider::SlopeBottomRight,
+///         _ => TileCollider::Empty
+///
+///     }
+///
+/// });
+///
+/// assert_eq!(tilemap.colliders().len(), 2);
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SepaxTilemap
+{
+
+    pub width: usize,
+    pub height: usize,
+    pub tile_size: (f32, f32),
+    pub origin: (f32, f32),
+    pub tiles: Vec<TileCollider>
+
+}
+
+/// A component added to each collider spawned from a [`SepaxTilemap`], storing the tilemap entity
+/// that it belongs to.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TilemapCollider
+{
+
+    pub tilemap: Entity
+
+}
+
+impl SepaxTilemap
+{
+
+    /// Create an empty tilemap.
+    pub fn new(width: usize, height: usize, tile_size: (f32, f32), origin: (f32, f32)) -> SepaxTilemap
+    {
+
+        SepaxTilemap { width, height, tile_size, origin, tiles: vec![TileCollider::Empty; width * height] }
+
+    }
+
+    /// Create a tilemap by calling the given function with the column and row of each tile,
+    /// starting from the bottom row. The function can return a [`TileCollider`] or a `bool`
+    /// which is true for solid tiles.
+    pub fn from_fn<T: Into<TileCollider>>(width: usize, height: usize, tile_size: (f32, f32), origin: (f32, f32), tile: impl Fn(usize, usize) -> T) -> SepaxTilemap
+    {
+
+        let tiles = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| tile(x, y).into()).collect();
+
+        SepaxTilemap { width, height, tile_size, origin, tiles }
+
+    }
+
+    /// The tile at the given column and row, or `Empty` if it is outside of the tilemap.
+    pub fn get(&self, x: usize, y: usize) -> TileCollider
+    {
+
+        if x >= self.width || y >= self.height
+        {
+
+            return TileCollider::Empty;
+
+        }
+
+        self.tiles.get((y * self.width) + x).copied().unwrap_or_default()
+
+    }
+
+    /// Sets the tile at the given column and row. Tiles outside of the tilemap are ignored.
+    pub fn set(&mut self, x: usize, y: usize, tile: TileCollider)
+    {
+
+        if x < self.width && y < self.height
+        {
+
+            if let Some(current) = self.tiles.get_mut((y * self.width) + x)
+            {
+
+                *current = tile;
+
+            }
+
+        }
+
+    }
+
+    /// The column and row of the tile containing the given point in world space, if any.
+    pub fn tile_at(&self, point: (f32, f32)) -> Option<(usize, usize)>
+    {
+
+        let x = ((point.0 - self.origin.0) / self.tile_size.0).floor();
+        let y = ((point.1 - self.origin.1) / self.tile_size.1).floor();
+
+        if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32
+        {
+
+            return None;
+
+        }
+
+        Some((x as usize, y as usize))
+
+    }
+
+    /// The shapes of the tilemap in world space: the merged solid tiles followed by the slopes.
+    pub fn colliders(&self) -> Vec<Convex>
+    {
+
+        let cells = self.width * self.height;
+        let solid: Vec<bool> = (0..cells).map(|i| self.tiles.get(i) == Some(&TileCollider::Solid)).collect();
+
+        let mut colliders = merge_grid(&solid, self.width, self.tile_size, self.origin);
+
+        for y in 0..self.height
+        {
+
+            for x in 0..self.width
+            {
+
+                let (width, height) = self.tile_size;
+                let corner = (self.origin.0 + ((x as f32) * width), self.origin.1 + ((y as f32) * height));
+
+                let (bottom_left, bottom_right, top_right, top_left) = ((0.0, 0.0), (width, 0.0), (width, height), (0.0, height));
+
+                //Counterclockwise, relative to the tile's bottom left corner
+                let triangle = match self.get(x, y)
+                {
+
+                    TileCollider::SlopeBottomLeft => [bottom_left, bottom_right, top_left],
+                    TileCollider::SlopeBottomRight => [bottom_left, bottom_right, top_right],
+                    TileCollider::SlopeTopLeft => [bottom_left, top_right, top_left],
+                    TileCollider::SlopeTopRight => [bottom_right, top_right, top_left],
+                    TileCollider::Empty | TileCollider::Solid => continue
+
+                };
+
+                //The triangle is positioned at its centroid so that collisions are resolved in the right direction
+                let centroid = ((triangle[0].0 + triangle[1].0 + triangle[2].0) / 3.0, (triangle[0].1 + triangle[1].1 + triangle[2].1) / 3.0);
+                let vertices = triangle.iter().map(|vertex| (vertex.0 - centroid.0, vertex.1 - centroid.1)).collect();
+
+                colliders.push(Convex::Polygon(Polygon::from_vertices((corner.0 + centroid.0, corner.1 + centroid.1), vertices)));
+
+            }
+
+        }
+
+        colliders
+
+    }
+
+}
+
+/// Spawns the colliders of each [`SepaxTilemap`] when it is added and rebuilds them whenever it
+/// changes, or its [`CollisionLayers`](crate::components::CollisionLayers) or
+/// [`Sensor`](crate::components::Sensor) are added, changed or removed. The colliders are
+/// separate immovable entities with a `Sepax` and a [`TilemapCollider`] component, and they are
+/// despawned along with the `SepaxTilemap` component.
+pub fn tilemap_system
+(
+    mut commands: Commands,
+    tilemaps: Query<(&SepaxTilemap, Option<&CollisionLayers>, Has<Sensor>)>,
+    changed: Query<Entity, (With<SepaxTilemap>, Or<(Changed<SepaxTilemap>, Changed<CollisionLayers>, Added<Sensor>)>)>,
+    colliders: Query<(Entity, &TilemapCollider)>,
+    mut removed: RemovedComponents<SepaxTilemap>,
+    mut removed_layers: RemovedComponents<CollisionLayers>,
+    mut removed_sensors: RemovedComponents<Sensor>
+)
+{
+
+    let removed: Vec<Entity> = removed.read().collect();
+
+    //The tilemaps to rebuild, in order so that their colliders are spawned in the same order each time
+    let rebuilt: BTreeSet<Entity> = changed.iter()
+    .chain(removed_layers.read().chain(removed_sensors.read()).filter(|entity| tilemaps.contains(*entity)))
+    .collect();
+
+    if rebuilt.is_empty() && removed.is_empty()
+    {
+
+        return;
+
+    }
+
+    for (entity, collider) in colliders.iter()
+    {
+
+        if removed.contains(&collider.tilemap) || rebuilt.contains(&collider.tilemap)
+        {
+
+            commands.entity(entity).despawn();
+
+        }
+
+    }
+
+    for tilemap_entity in rebuilt
+    {
+
+        let Ok((tilemap, layers, sensor)) = tilemaps.get(tilemap_entity) else { continue; };
+
+        for convex in tilemap.colliders()
+        {
+
+            let mut spawned = commands.spawn((Sepax { convex }, TilemapCollider { tilemap: tilemap_entity }));
+
+            if let Some(layers) = layers
+            {
+
+                spawned.insert(*layers);
+
+            }
+
+            if sensor
+            {
+
+                spawned.insert(Sensor);
+
+            }
+
+        }
+
+    }
+
+}
+
+#[cfg(test)]
+mod tilemap_tests
+{
+
+    use super::*;
+
+    fn count(app: &mut App) -> usize
+    {
+
+        app.world.query::<(&Sepax, &TilemapCollider)>().iter(&app.world).count()
+
+    }
+
+    #[test]
+    fn test_colliders()
+    {
+
+        //A room whose floor has a slope in each bottom corner
+        let mut tilemap = SepaxTilemap::from_fn(10, 6, (8.0, 8.0), (-40.0, 0.0), |x, y| x == 0 || x == 9 || y == 0 || y == 5);
+        tilemap.set(1, 1, TileCollider::SlopeBottomLeft);
+        tilemap.set(8, 1, TileCollider::SlopeBottomRight);
+        tilemap.set(20, 1, TileCollider::Solid);
+
+        assert_eq!(tilemap.get(1, 1), TileCollider::SlopeBottomLeft);
+        assert_eq!(tilemap.get(20, 1), TileCollider::Empty);
+        assert_eq!(tilemap.tile_at((-36.0, 12.0)), Some((0, 1)));
+        assert_eq!(tilemap.tile_at((-41.0, 12.0)), None);
+
+        let colliders = tilemap.colliders();
+        assert_eq!(colliders.len(), 6);
+
+        //The slope in the bottom left corner rises towards the left
+        let slope = colliders[4].shape();
+        assert!(contains_point(slope, (-31.0, 9.0)));
+        assert!(!contains_point(slope, (-25.0, 15.0)));
+
+    }
+
+    #[test]
+    fn test_tilemap_system()
+    {
+
+        let mut app = App::new();
+        app.add_systems(Update, tilemap_system);
+
+        let tilemap = app.world.spawn((SepaxTilemap::from_fn(4, 1, (8.0, 8.0), (0.0, 0.0), |_x, _y| true), CollisionLayers::new(0b10, 0b10))).id();
+
+        app.update();
+        assert_eq!(count(&mut app), 1);
+
+        let layers = app.world.query::<(&TilemapCollider, &CollisionLayers)>().iter(&app.world).map(|(_collider, layers)| *layers).collect::<Vec<_>>();
+        assert_eq!(layers, vec![CollisionLayers::new(0b10, 0b10)]);
+
+        //Removing the layers or adding and removing a Sensor updates the colliders
+        app.world.entity_mut(tilemap).remove::<CollisionLayers>().insert(Sensor);
+
+        app.update();
+        assert_eq!(count(&mut app), 1);
+        assert_eq!(app.world.query_filtered::<(), (With<TilemapCollider>, With<CollisionLayers>)>().iter(&app.world).count(), 0);
+        assert_eq!(app.world.query_filtered::<(), (With<TilemapCollider>, With<Sensor>)>().iter(&app.world).count(), 1);
+
+        app.world.entity_mut(tilemap).remove::<Sensor>();
+
+        app.update();
+        assert_eq!(count(&mut app), 1);
+        assert_eq!(app.world.query_filtered::<(), (With<TilemapCollider>, With<Sensor>)>().iter(&app.world).count(), 0);
+
+        //Removing a tile in the middle splits the floor in two
+        app.world.get_mut::<SepaxTilemap>(tilemap).unwrap().set(1, 0, TileCollider::Empty);
+
+        app.update();
+        assert_eq!(count(&mut app), 2);
+
+        app.world.entity_mut(tilemap).remove::<SepaxTilemap>();
+
+        app.update();
+        assert_eq!(count(&mut app), 0);
+
+    }
+
+}